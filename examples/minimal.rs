@@ -40,11 +40,8 @@ fn setup(mut cmds: Commands) {
     let window = cmds.spawn(MainWindow).id();
     cmds.push(SpawnSpatialEguiWindowCommand {
         target_entity: Some(window),
-        position: Vec3::ZERO,
-        rotation: Quat::IDENTITY,
-        resolution: UVec2::splat(512),
-        height: 1.0,
-        unlit: true,
+        resizable: true,
+        ..default()
     });
     cmds.spawn(Camera3dBundle::default())
         .insert(Transform::from_xyz(1.0, 3.0, -5.0).looking_at(Vec3::ZERO, Vec3::Y))
@@ -2,6 +2,7 @@ use bevy::prelude::*;
 use bevy_egui::egui;
 use bevy_egui::EguiContext;
 use bevy_egui::EguiPlugin;
+use bevy_mod_openxr::add_xr_plugins;
 use bevy_panorbit_camera::PanOrbitCamera;
 use bevy_panorbit_camera::PanOrbitCameraPlugin;
 use bevy_spatial_egui::SpawnSpatialEguiWindowCommand;
@@ -10,7 +11,6 @@ use bevy_suis::window_pointers::SuisWindowPointerPlugin;
 use bevy_suis::xr::SuisXrPlugin;
 use bevy_suis::xr_controllers::SuisXrControllerPlugin;
 use bevy_suis::SuisCorePlugin;
-use bevy_mod_openxr::add_xr_plugins;
 
 fn main() -> AppExit {
     App::new()
@@ -49,10 +49,8 @@ fn setup(mut cmds: Commands) {
     cmds.push(SpawnSpatialEguiWindowCommand {
         target_entity: Some(window),
         position: Vec3::new(0.0, 1.0, -0.5),
-        rotation: Quat::IDENTITY,
-        resolution: UVec2::splat(512),
-        height: 1.0,
-        unlit: true,
+        resizable: true,
+        ..default()
     });
     cmds.spawn(Camera3dBundle::default())
         .insert(Transform::from_xyz(1.0, 3.0, -5.0).looking_at(Vec3::ZERO, Vec3::Y))
@@ -0,0 +1,168 @@
+use bevy::prelude::*;
+use bevy_suis::{
+    window_pointers::MouseInputMethodData, xr::HandInputMethodData,
+    xr_controllers::XrControllerInputMethodData,
+};
+
+/// Maps input method buttons to spatial window actions, the resource applies to all windows and
+/// can be overridden by inserting it as a component on a window.
+///
+/// The mouse grabs with the right button by default, bind `mouse_secondary` to it and move
+/// `mouse_grab` elsewhere to open context menus with a right click instead.
+#[derive(Resource, Component, Clone, Copy, Debug)]
+pub struct SpatialEguiInputBindings {
+    pub mouse_click: Option<SpatialEguiMouseButton>,
+    pub mouse_grab: Option<SpatialEguiMouseButton>,
+    pub mouse_secondary: Option<SpatialEguiMouseButton>,
+    pub mouse_middle: Option<SpatialEguiMouseButton>,
+    /// Multiplier for mouse wheel and touchpad scrolling
+    pub mouse_scroll_sensitivity: f32,
+    pub controller_click: Option<SpatialEguiControllerButton>,
+    pub controller_grab: Option<SpatialEguiControllerButton>,
+    pub controller_secondary: Option<SpatialEguiControllerButton>,
+    pub controller_middle: Option<SpatialEguiControllerButton>,
+    /// Pixels per second scrolled at full stick deflection
+    pub stick_scroll_speed: f32,
+    /// Stick deflection below which no scrolling happens
+    pub stick_dead_zone: f32,
+    pub hand_click: Option<SpatialEguiHandGesture>,
+    pub hand_secondary: Option<SpatialEguiHandGesture>,
+    pub hand_middle: Option<SpatialEguiHandGesture>,
+}
+
+impl Default for SpatialEguiInputBindings {
+    fn default() -> Self {
+        Self {
+            mouse_click: Some(SpatialEguiMouseButton::Left),
+            mouse_grab: Some(SpatialEguiMouseButton::Right),
+            mouse_secondary: None,
+            mouse_middle: Some(SpatialEguiMouseButton::Middle),
+            mouse_scroll_sensitivity: 1.0,
+            controller_click: Some(SpatialEguiControllerButton::Trigger),
+            controller_grab: Some(SpatialEguiControllerButton::Squeeze),
+            controller_secondary: Some(SpatialEguiControllerButton::A),
+            controller_middle: Some(SpatialEguiControllerButton::B),
+            stick_scroll_speed: 1000.0,
+            stick_dead_zone: 0.1,
+            hand_click: Some(SpatialEguiHandGesture::Pinch),
+            hand_secondary: Some(SpatialEguiHandGesture::MiddlePinch),
+            hand_middle: None,
+        }
+    }
+}
+
+impl SpatialEguiInputBindings {
+    pub(crate) fn stick_scroll(&self, stick_pos: Vec2, delta_seconds: f32) -> Vec2 {
+        if stick_pos.length() < self.stick_dead_zone {
+            return Vec2::ZERO;
+        }
+        stick_pos * delta_seconds * self.stick_scroll_speed
+    }
+
+    /// Whether any bound button of the method is held
+    pub(crate) fn any_pressed(
+        &self,
+        controller: Option<&XrControllerInputMethodData>,
+        face_buttons: Option<&SpatialEguiControllerFaceButtons>,
+        mouse: Option<&MouseInputMethodData>,
+    ) -> bool {
+        let controller_pressed = controller.is_some_and(|controller| {
+            [
+                self.controller_click,
+                self.controller_grab,
+                self.controller_secondary,
+                self.controller_middle,
+            ]
+            .into_iter()
+            .flatten()
+            .any(|button| button.pressed(controller, face_buttons))
+        });
+        let mouse_pressed = mouse.is_some_and(|mouse| {
+            [
+                self.mouse_click,
+                self.mouse_grab,
+                self.mouse_secondary,
+                self.mouse_middle,
+            ]
+            .into_iter()
+            .flatten()
+            .any(|button| button.pressed(mouse))
+        });
+        controller_pressed || mouse_pressed
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpatialEguiMouseButton {
+    Left,
+    Right,
+    Middle,
+}
+
+impl SpatialEguiMouseButton {
+    pub fn pressed(&self, mouse: &MouseInputMethodData) -> bool {
+        match self {
+            SpatialEguiMouseButton::Left => mouse.left_button.pressed,
+            SpatialEguiMouseButton::Right => mouse.right_button.pressed,
+            SpatialEguiMouseButton::Middle => mouse.middle_button.pressed,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpatialEguiControllerButton {
+    Trigger,
+    Squeeze,
+    /// Read from [`SpatialEguiControllerFaceButtons`]
+    A,
+    /// Read from [`SpatialEguiControllerFaceButtons`]
+    B,
+}
+
+impl SpatialEguiControllerButton {
+    pub fn pressed(
+        &self,
+        controller: &XrControllerInputMethodData,
+        face_buttons: Option<&SpatialEguiControllerFaceButtons>,
+    ) -> bool {
+        match self {
+            SpatialEguiControllerButton::Trigger => controller.trigger_pulled,
+            SpatialEguiControllerButton::Squeeze => controller.squeezed,
+            SpatialEguiControllerButton::A => face_buttons.is_some_and(|buttons| buttons.a),
+            SpatialEguiControllerButton::B => face_buttons.is_some_and(|buttons| buttons.b),
+        }
+    }
+}
+
+/// The face buttons of an xr controller, bevy_suis doesn't track these so insert this on the
+/// controller input method and keep it updated from your own xr actions
+#[derive(Clone, Copy, Component, Debug, Default)]
+pub struct SpatialEguiControllerFaceButtons {
+    pub a: bool,
+    pub b: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpatialEguiHandGesture {
+    /// Index finger and thumb tips touching
+    Pinch,
+    /// Middle finger and thumb tips touching
+    MiddlePinch,
+}
+
+impl SpatialEguiHandGesture {
+    /// `space` is the location of the window the gesture is performed on
+    pub fn active(&self, hand: &HandInputMethodData, space: &GlobalTransform) -> bool {
+        let hand = hand.get_in_relative_space(space);
+        match self {
+            SpatialEguiHandGesture::Pinch => {
+                hand.index.tip.pos.distance(hand.thumb.tip.pos)
+                    < (0.002 + hand.index.tip.radius + hand.thumb.tip.radius)
+            }
+            SpatialEguiHandGesture::MiddlePinch => {
+                hand.middle.tip.pos.distance(hand.thumb.tip.pos)
+                    < (0.002 + hand.middle.tip.radius + hand.thumb.tip.radius)
+            }
+        }
+    }
+}
@@ -0,0 +1,348 @@
+use bevy::{
+    color::palettes::css,
+    ecs::entity::{EntityHashMap, EntityHashSet},
+    prelude::*,
+    utils::HashSet,
+};
+use bevy_suis::{Field, InputHandler};
+
+use crate::{
+    input_surface_capture_condition, minimize::MinimizedSpatialEguiWindow,
+    ImmovableSpatialEguiWindow, MethodTarget, SpatialEguiWindowPhysicalSize, WindowInput,
+};
+
+/// Adds 3D window chrome around the egui surface of a window. Every element is a child entity
+/// with its own input handler, so managing the window never gets in the way of its widgets.
+#[derive(Clone, Copy, Component, Debug)]
+pub struct SpatialEguiWindowChrome {
+    /// Where the grab bar goes, `None` for no grab bar
+    pub bar: Option<SpatialEguiChromeBarPosition>,
+    /// Height of the grab bar and size of the buttons in meters
+    pub bar_height: f32,
+    /// Space between the window and its chrome in meters
+    pub gap: f32,
+    pub close: bool,
+    pub minimize: bool,
+    pub pin: bool,
+    pub resize_corner: bool,
+    pub bar_color: Color,
+}
+
+impl Default for SpatialEguiWindowChrome {
+    fn default() -> Self {
+        Self {
+            bar: Some(SpatialEguiChromeBarPosition::Below),
+            bar_height: 0.04,
+            gap: 0.01,
+            close: true,
+            minimize: true,
+            pin: true,
+            resize_corner: true,
+            bar_color: css::DIM_GRAY.into(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpatialEguiChromeBarPosition {
+    Above,
+    Below,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SpatialEguiChromeKind {
+    GrabBar,
+    Close,
+    Minimize,
+    Pin,
+    ResizeCorner,
+}
+
+impl SpatialEguiChromeKind {
+    fn color(&self, chrome: &SpatialEguiWindowChrome) -> Color {
+        match self {
+            SpatialEguiChromeKind::GrabBar => chrome.bar_color,
+            SpatialEguiChromeKind::Close => css::INDIAN_RED.into(),
+            SpatialEguiChromeKind::Minimize => css::GOLDENROD.into(),
+            SpatialEguiChromeKind::Pin => css::STEEL_BLUE.into(),
+            SpatialEguiChromeKind::ResizeCorner => chrome.bar_color,
+        }
+    }
+}
+
+/// A piece of chrome of `window`
+#[derive(Clone, Copy, Component, Debug)]
+pub struct SpatialEguiChromeElement {
+    pub window: Entity,
+    pub kind: SpatialEguiChromeKind,
+    /// Size of the element in meters
+    pub size: Vec3,
+}
+
+/// A chrome button was pressed, the button's action runs in [`Last`]
+#[derive(Event, Clone, Copy, Debug)]
+pub struct SpatialEguiChromeButtonPressed {
+    pub window: Entity,
+    pub input_method: Entity,
+    pub button: SpatialEguiChromeKind,
+}
+
+/// The chrome entities of a window
+#[derive(Clone, Component, Default)]
+pub(crate) struct ChromeElements(Vec<Entity>);
+
+/// Transforms and sizes of the chrome elements in window space
+fn chrome_layout(
+    chrome: &SpatialEguiWindowChrome,
+    size: Vec3,
+) -> Vec<(SpatialEguiChromeKind, Vec3, Vec3)> {
+    let mut layout = Vec::new();
+    let half_size = size.xy() / 2.0;
+    let button_size = Vec3::new(chrome.bar_height, chrome.bar_height, size.z);
+    let buttons = [
+        (chrome.close, SpatialEguiChromeKind::Close),
+        (chrome.minimize, SpatialEguiChromeKind::Minimize),
+        (chrome.pin, SpatialEguiChromeKind::Pin),
+    ]
+    .into_iter()
+    .filter_map(|(enabled, kind)| enabled.then_some(kind))
+    .collect::<Vec<_>>();
+    let bar_y = half_size.y + chrome.gap + (chrome.bar_height / 2.0);
+    let bar_y = match chrome.bar {
+        Some(SpatialEguiChromeBarPosition::Above) | None => bar_y,
+        Some(SpatialEguiChromeBarPosition::Below) => -bar_y,
+    };
+    // seen from the egui side +X is on the left, the buttons go on the right of the bar
+    let button_step = chrome.bar_height + chrome.gap;
+    for (i, kind) in buttons.iter().enumerate() {
+        let x = -half_size.x + (chrome.bar_height / 2.0) + (button_step * i as f32);
+        layout.push((*kind, Vec3::new(x, bar_y, 0.0), button_size));
+    }
+    if chrome.bar.is_some() {
+        let buttons_width = button_step * buttons.len() as f32;
+        let width = (size.x - (2.0 * buttons_width)).max(chrome.bar_height);
+        layout.push((
+            SpatialEguiChromeKind::GrabBar,
+            Vec3::new(0.0, bar_y, 0.0),
+            Vec3::new(width, chrome.bar_height, size.z),
+        ));
+    }
+    if chrome.resize_corner {
+        let offset = chrome.gap + (chrome.bar_height / 2.0);
+        layout.push((
+            SpatialEguiChromeKind::ResizeCorner,
+            Vec3::new(-half_size.x - offset, -half_size.y - offset, 0.0),
+            button_size,
+        ));
+    }
+    layout
+}
+
+pub(crate) fn spawn_window_chrome(
+    windows: Query<
+        (
+            Entity,
+            &SpatialEguiWindowChrome,
+            &SpatialEguiWindowPhysicalSize,
+            Option<&ChromeElements>,
+        ),
+        Or<(
+            Changed<SpatialEguiWindowChrome>,
+            Added<SpatialEguiWindowPhysicalSize>,
+        )>,
+    >,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut cmds: Commands,
+) {
+    for (window, chrome, size, elements) in &windows {
+        for element in elements.iter().flat_map(|elements| &elements.0) {
+            cmds.entity(*element).despawn_recursive();
+        }
+        let elements = chrome_layout(chrome, size.0)
+            .into_iter()
+            .map(|(kind, translation, element_size)| {
+                cmds.spawn((
+                    PbrBundle {
+                        mesh: meshes.add(Cuboid::from_size(element_size)),
+                        material: materials.add(StandardMaterial {
+                            base_color: kind.color(chrome),
+                            unlit: true,
+                            ..default()
+                        }),
+                        transform: Transform::from_translation(translation),
+                        ..default()
+                    },
+                    Field::Cuboid(Cuboid::from_size(element_size)),
+                    InputHandler::new(input_surface_capture_condition),
+                    SpatialEguiChromeElement {
+                        window,
+                        kind,
+                        size: element_size,
+                    },
+                ))
+                .set_parent(window)
+                .id()
+            })
+            .collect();
+        cmds.entity(window).insert(ChromeElements(elements));
+    }
+}
+
+/// Moves the chrome along when the window is resized, the elements are kept so a grab on them
+/// isn't interrupted
+pub(crate) fn layout_window_chrome(
+    windows: Query<
+        (
+            &SpatialEguiWindowChrome,
+            &SpatialEguiWindowPhysicalSize,
+            &ChromeElements,
+        ),
+        Changed<SpatialEguiWindowPhysicalSize>,
+    >,
+    mut elements: Query<(
+        &mut SpatialEguiChromeElement,
+        &mut Transform,
+        &mut Field,
+        &Handle<Mesh>,
+    )>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    for (chrome, size, chrome_elements) in &windows {
+        let layout = chrome_layout(chrome, size.0);
+        for entity in &chrome_elements.0 {
+            let Ok((mut element, mut transform, mut field, mesh)) = elements.get_mut(*entity)
+            else {
+                continue;
+            };
+            let Some((_, translation, element_size)) =
+                layout.iter().find(|(kind, ..)| *kind == element.kind)
+            else {
+                continue;
+            };
+            transform.translation = *translation;
+            element.size = *element_size;
+            *field = Field::Cuboid(Cuboid::from_size(*element_size));
+            meshes.insert(mesh, Cuboid::from_size(*element_size).into());
+        }
+    }
+}
+
+/// Hands input on the grab bar and resize corner to the window and runs the buttons
+pub(crate) fn route_chrome_input(
+    In(inputs): In<Vec<WindowInput>>,
+    elements: Query<(&SpatialEguiChromeElement, &GlobalTransform)>,
+    gt_query: Query<&GlobalTransform>,
+    mut pressed_buttons: Local<EntityHashMap<EntityHashSet>>,
+    mut button_events: EventWriter<SpatialEguiChromeButtonPressed>,
+) -> Vec<WindowInput> {
+    let mut window_inputs = Vec::with_capacity(inputs.len());
+    let mut chrome_inputs = Vec::new();
+    for input in inputs {
+        match elements.get(input.handler) {
+            Ok((element, element_gt)) => chrome_inputs.push((*element, *element_gt, input)),
+            Err(_) => window_inputs.push(input),
+        }
+    }
+    let mut next_pressed = EntityHashMap::<EntityHashSet>::default();
+    for (element, element_gt, input) in chrome_inputs {
+        let Ok(window_gt) = gt_query.get(element.window) else {
+            continue;
+        };
+        let target = match element.kind {
+            SpatialEguiChromeKind::GrabBar => MethodTarget::Move,
+            SpatialEguiChromeKind::ResizeCorner => MethodTarget::Resize(-Vec2::ONE),
+            button => {
+                let last_pressed = pressed_buttons.remove(&input.handler).unwrap_or_default();
+                let pressed = input
+                    .methods
+                    .iter()
+                    .filter(|method| method.state.click)
+                    .map(|method| method.input_method)
+                    .collect::<EntityHashSet>();
+                button_events.send_batch(
+                    pressed
+                        .iter()
+                        .filter(|method| !last_pressed.contains(*method))
+                        .map(|input_method| SpatialEguiChromeButtonPressed {
+                            window: element.window,
+                            input_method: *input_method,
+                            button,
+                        }),
+                );
+                next_pressed.insert(input.handler, pressed);
+                continue;
+            }
+        };
+        let to_window = window_gt.affine().inverse() * element_gt.affine();
+        let methods = input.methods.into_iter().map(|mut method| {
+            method.closest_point = to_window.transform_point3(method.closest_point);
+            method.input_method_location = Transform::from_matrix(
+                Mat4::from(to_window) * method.input_method_location.compute_matrix(),
+            );
+            // clicking the chrome grabs it, there is nothing else to click on
+            method.state.grab |= method.state.click;
+            method.state.click = false;
+            method.target = target;
+            method
+        });
+        match window_inputs
+            .iter_mut()
+            .find(|input| input.handler == element.window)
+        {
+            Some(window_input) => {
+                window_input.captured |= input.captured;
+                window_input.methods.extend(methods);
+            }
+            None => window_inputs.push(WindowInput {
+                handler: element.window,
+                handler_location: *window_gt,
+                captured: input.captured,
+                methods: methods.collect(),
+            }),
+        }
+    }
+    *pressed_buttons = next_pressed;
+    window_inputs
+}
+
+pub(crate) fn run_chrome_buttons(
+    mut pressed: EventReader<SpatialEguiChromeButtonPressed>,
+    immovable: Query<Has<ImmovableSpatialEguiWindow>>,
+    mut cmds: Commands,
+) {
+    // several methods pressing the same button in one frame only press it once
+    let mut seen = HashSet::new();
+    for event in pressed.read() {
+        if seen.insert((event.window, event.button)) {
+            press_button(event.window, event.button, &immovable, &mut cmds);
+        }
+    }
+}
+
+fn press_button(
+    window: Entity,
+    button: SpatialEguiChromeKind,
+    immovable: &Query<Has<ImmovableSpatialEguiWindow>>,
+    cmds: &mut Commands,
+) {
+    let Some(mut window_cmds) = cmds.get_entity(window) else {
+        return;
+    };
+    match button {
+        SpatialEguiChromeKind::Close => {
+            window_cmds.despawn_recursive();
+        }
+        SpatialEguiChromeKind::Minimize => {
+            window_cmds.insert(MinimizedSpatialEguiWindow);
+        }
+        SpatialEguiChromeKind::Pin => {
+            if immovable.get(window).unwrap_or_default() {
+                window_cmds.remove::<ImmovableSpatialEguiWindow>();
+            } else {
+                window_cmds.insert(ImmovableSpatialEguiWindow);
+            }
+        }
+        SpatialEguiChromeKind::GrabBar | SpatialEguiChromeKind::ResizeCorner => {}
+    }
+}
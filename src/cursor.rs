@@ -0,0 +1,80 @@
+use bevy::{color::palettes::css, prelude::*};
+
+/// Configures the reticles drawn where input methods point at spatial windows
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct SpatialEguiCursorSettings {
+    pub enabled: bool,
+    /// Radius of the reticle in meters
+    pub radius: f32,
+    pub hover_color: Color,
+    pub press_color: Color,
+    pub grab_color: Color,
+}
+
+impl Default for SpatialEguiCursorSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            radius: 0.005,
+            hover_color: css::WHITE.into(),
+            press_color: css::DEEP_SKY_BLUE.into(),
+            grab_color: css::ORANGE.into(),
+        }
+    }
+}
+
+impl SpatialEguiCursorSettings {
+    pub fn color(&self, state: SpatialEguiCursorState) -> Color {
+        match state {
+            SpatialEguiCursorState::Hover => self.hover_color,
+            SpatialEguiCursorState::Press => self.press_color,
+            SpatialEguiCursorState::Grab => self.grab_color,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpatialEguiCursorState {
+    Hover,
+    Press,
+    Grab,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SpatialEguiCursor {
+    pub input_method: Entity,
+    /// Where the input method hits the window, in window space
+    pub position: Vec3,
+    pub state: SpatialEguiCursorState,
+}
+
+/// The cursors of all input methods interacting with a window this frame
+#[derive(Clone, Component, Debug, Default)]
+pub struct SpatialEguiWindowCursors(pub Vec<SpatialEguiCursor>);
+
+/// How far in front of the window surface the reticle is drawn, avoids z-fighting
+const CURSOR_SURFACE_OFFSET: f32 = 0.001;
+
+pub(crate) fn draw_cursors(
+    settings: Res<SpatialEguiCursorSettings>,
+    windows: Query<(&SpatialEguiWindowCursors, &GlobalTransform)>,
+    mut giz: Gizmos,
+) {
+    if !settings.enabled {
+        return;
+    }
+    for (cursors, window_gt) in &windows {
+        let normal =
+            Dir3::new(window_gt.affine().transform_vector3(Vec3::NEG_Z)).unwrap_or(Dir3::NEG_Z);
+        for cursor in &cursors.0 {
+            let position =
+                window_gt.transform_point(cursor.position) + (normal * CURSOR_SURFACE_OFFSET);
+            giz.circle(
+                position,
+                normal,
+                settings.radius,
+                settings.color(cursor.state),
+            );
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+/// A window got its config applied for the first time
+#[derive(Event, Clone, Copy, Debug)]
+pub struct SpatialEguiWindowSpawned {
+    pub window: Entity,
+}
+
+/// A window was despawned or stopped being a spatial window
+#[derive(Event, Clone, Copy, Debug)]
+pub struct SpatialEguiWindowClosed {
+    pub window: Entity,
+}
+
+/// An input method started hovering a window
+#[derive(Event, Clone, Copy, Debug)]
+pub struct SpatialEguiWindowHovered {
+    pub window: Entity,
+    pub input_method: Entity,
+}
+
+/// An input method started clicking on the surface of a window
+#[derive(Event, Clone, Copy, Debug)]
+pub struct SpatialEguiWindowPressed {
+    pub window: Entity,
+    pub input_method: Entity,
+}
+
+/// An input method clicked a window and moved the keyboard focus to it
+#[derive(Event, Clone, Copy, Debug)]
+pub struct SpatialEguiWindowFocused {
+    pub window: Entity,
+    pub input_method: Entity,
+}
+
+/// An input method started moving or resizing a window
+#[derive(Event, Clone, Copy, Debug)]
+pub struct SpatialEguiWindowGrabbed {
+    pub window: Entity,
+    pub input_method: Entity,
+}
+
+/// An input method let go of a window it was moving or resizing
+#[derive(Event, Clone, Copy, Debug)]
+pub struct SpatialEguiWindowReleased {
+    pub window: Entity,
+    pub input_method: Entity,
+}
+
+/// A grabbed window was moved, `transform` is the new local transform of the window
+#[derive(Event, Clone, Copy, Debug)]
+pub struct SpatialEguiWindowMoved {
+    pub window: Entity,
+    pub input_method: Entity,
+    pub transform: Transform,
+}
+
+#[derive(SystemParam)]
+pub(crate) struct WindowEventWriters<'w> {
+    pub(crate) hovered: EventWriter<'w, SpatialEguiWindowHovered>,
+    pub(crate) pressed: EventWriter<'w, SpatialEguiWindowPressed>,
+    pub(crate) focused: EventWriter<'w, SpatialEguiWindowFocused>,
+    pub(crate) grabbed: EventWriter<'w, SpatialEguiWindowGrabbed>,
+    pub(crate) released: EventWriter<'w, SpatialEguiWindowReleased>,
+    pub(crate) moved: EventWriter<'w, SpatialEguiWindowMoved>,
+}
+
+pub(crate) fn add_window_events(app: &mut App) {
+    app.add_event::<SpatialEguiWindowSpawned>()
+        .add_event::<SpatialEguiWindowClosed>()
+        .add_event::<SpatialEguiWindowHovered>()
+        .add_event::<SpatialEguiWindowPressed>()
+        .add_event::<SpatialEguiWindowFocused>()
+        .add_event::<SpatialEguiWindowGrabbed>()
+        .add_event::<SpatialEguiWindowReleased>()
+        .add_event::<SpatialEguiWindowMoved>();
+}
+
+pub(crate) fn send_closed_events(
+    mut removed: RemovedComponents<crate::SpatialEguiWindow>,
+    mut closed: EventWriter<SpatialEguiWindowClosed>,
+) {
+    closed.send_batch(
+        removed
+            .read()
+            .map(|window| SpatialEguiWindowClosed { window }),
+    );
+}
@@ -0,0 +1,163 @@
+use bevy::{ecs::entity::EntityHashMap, prelude::*};
+
+use crate::{placement::facing, resize::SpatialEguiWindowResizable};
+
+/// How a window follows the input methods grabbing it, windows without this component use the
+/// default, which rigidly attaches them to the grabbing method
+#[derive(Clone, Copy, Component, Debug, Default)]
+pub struct SpatialEguiGrabSettings {
+    /// Lock roll and pitch so the window stays upright while dragged
+    pub upright: bool,
+    /// Turn the egui side of the window toward this entity while dragged, like the camera or head
+    pub face_viewer: Option<Entity>,
+    /// Time in seconds it takes the window to cover most of the way to the grabbing method, 0.0
+    /// follows it rigidly. Filters out hand jitter.
+    pub smoothing: f32,
+    /// Grabbing with a second input method scales the window by the distance between both methods
+    pub two_handed_scale: bool,
+}
+
+impl SpatialEguiGrabSettings {
+    /// Applies the rotation options to where the grabbing method would put the window
+    pub(crate) fn target(&self, target: Transform, viewer: Option<Vec3>) -> Transform {
+        let rotation = match viewer {
+            Some(viewer) => facing(target.translation, viewer, self.upright),
+            None if self.upright => {
+                let forward = target.forward().with_y(0.0);
+                facing(target.translation, target.translation + forward, true)
+            }
+            None => None,
+        };
+        Transform {
+            rotation: rotation.unwrap_or(target.rotation),
+            ..target
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct TwoHandedGrab {
+    input_method: Entity,
+    start_distance: f32,
+    start_height: f32,
+}
+
+#[derive(Clone, Copy, Component)]
+pub(crate) struct GrabbedEguiWindow {
+    /// The method the window is attached to
+    pub(crate) input_method: Entity,
+    method_relative_transform: Transform,
+    two_handed: Option<TwoHandedGrab>,
+}
+
+impl GrabbedEguiWindow {
+    pub(crate) fn new(
+        input_method: Entity,
+        method_gt: &GlobalTransform,
+        window_gt: &GlobalTransform,
+    ) -> Self {
+        Self {
+            input_method,
+            method_relative_transform: window_gt.reparented_to(method_gt),
+            two_handed: None,
+        }
+    }
+
+    /// Whether `input_method` is one of the methods grabbing the window
+    pub(crate) fn holds(&self, input_method: Entity) -> bool {
+        self.input_method == input_method
+            || self
+                .two_handed
+                .is_some_and(|second| second.input_method == input_method)
+    }
+
+    /// Starts scaling the window with a second method, returns `false` if there already are two
+    pub(crate) fn add_second_method(
+        &mut self,
+        input_method: Entity,
+        method_gts: &EntityHashMap<GlobalTransform>,
+        height: f32,
+    ) -> bool {
+        if self.holds(input_method) || self.two_handed.is_some() {
+            return false;
+        }
+        let (Some(first), Some(second)) = (
+            method_gts.get(&self.input_method),
+            method_gts.get(&input_method),
+        ) else {
+            return false;
+        };
+        let start_distance = first.translation().distance(second.translation());
+        if start_distance < f32::EPSILON {
+            return false;
+        }
+        self.two_handed = Some(TwoHandedGrab {
+            input_method,
+            start_distance,
+            start_height: height,
+        });
+        true
+    }
+
+    /// Lets go with `input_method`, returns whether another method still holds the window
+    pub(crate) fn release(
+        &mut self,
+        input_method: Entity,
+        method_gts: &EntityHashMap<GlobalTransform>,
+        window_gt: &GlobalTransform,
+    ) -> bool {
+        let Some(second) = self.two_handed.take() else {
+            return false;
+        };
+        if second.input_method == input_method {
+            return true;
+        }
+        // the second method takes over
+        match method_gts.get(&second.input_method) {
+            Some(method_gt) => {
+                *self = Self::new(second.input_method, method_gt, window_gt);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Moves the window away from the grabbing method
+    pub(crate) fn push(&mut self, distance: f32) {
+        self.method_relative_transform.translation.z += distance;
+    }
+
+    /// Where the grabbing method puts the window
+    pub(crate) fn target(&self, method_gt: &GlobalTransform) -> Transform {
+        method_gt
+            .mul_transform(self.method_relative_transform)
+            .compute_transform()
+    }
+
+    /// The window height following the distance between both methods of a two handed grab, within
+    /// the size bounds of `resizable`. `aspect_ratio` is the physical width over height
+    pub(crate) fn two_handed_height(
+        &self,
+        method_gts: &EntityHashMap<GlobalTransform>,
+        resizable: Option<&SpatialEguiWindowResizable>,
+        aspect_ratio: f32,
+    ) -> Option<f32> {
+        let second = self.two_handed?;
+        let first_gt = method_gts.get(&self.input_method)?;
+        let second_gt = method_gts.get(&second.input_method)?;
+        let distance = first_gt.translation().distance(second_gt.translation());
+        let height = second.start_height * (distance / second.start_distance);
+        // windows that aren't resizable still keep to the default bounds
+        let resizable = resizable.copied().unwrap_or_default();
+        // the bounds cross for extreme aspect ratios, the maximum wins then
+        let min = resizable
+            .min_size
+            .y
+            .max(resizable.min_size.x / aspect_ratio);
+        let max = resizable
+            .max_size
+            .y
+            .min(resizable.max_size.x / aspect_ratio);
+        Some(height.max(min).min(max))
+    }
+}
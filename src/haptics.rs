@@ -0,0 +1,118 @@
+use std::{marker::PhantomData, time::Duration};
+
+use bevy::{ecs::world::FromWorld, prelude::*};
+use bevy_suis::xr_controllers::XrControllerInputMethodData;
+
+use crate::{
+    events::{
+        SpatialEguiWindowGrabbed, SpatialEguiWindowHovered, SpatialEguiWindowPressed,
+        SpatialEguiWindowReleased,
+    },
+    simulated::SimulatedSpatialPointer,
+};
+
+/// Something that can play haptic pulses on input methods, like an OpenXR haptic action
+pub trait SpatialEguiHaptics: Send + Sync + 'static {
+    fn pulse(&mut self, input_method: Entity, pulse: SpatialEguiHapticPulse);
+}
+
+/// Sends haptic pulses for window interactions of XR controllers and simulated pointers to `B`
+pub struct SpatialEguiHapticsPlugin<B>(PhantomData<B>);
+
+impl<B> Default for SpatialEguiHapticsPlugin<B> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<B: SpatialEguiHaptics + Resource + FromWorld> Plugin for SpatialEguiHapticsPlugin<B> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpatialEguiHapticsSettings>();
+        app.init_resource::<B>();
+        // runs in Last so all events sent by update_windows in PostUpdate are seen this frame
+        app.add_systems(Last, send_haptics::<B>);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpatialEguiHapticPulse {
+    /// Between 0.0 and 1.0
+    pub amplitude: f32,
+    pub duration: Duration,
+}
+
+impl SpatialEguiHapticPulse {
+    pub const fn new(amplitude: f32, duration: Duration) -> Self {
+        Self {
+            amplitude,
+            duration,
+        }
+    }
+}
+
+/// Pulses played for each kind of interaction, `None` disables the pulse
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct SpatialEguiHapticsSettings {
+    /// An input method started hovering a window
+    pub hover: Option<SpatialEguiHapticPulse>,
+    /// An input method started clicking a window
+    pub press: Option<SpatialEguiHapticPulse>,
+    /// An input method started moving or resizing a window
+    pub grab: Option<SpatialEguiHapticPulse>,
+    /// An input method let go of a window
+    pub release: Option<SpatialEguiHapticPulse>,
+}
+
+impl Default for SpatialEguiHapticsSettings {
+    fn default() -> Self {
+        Self {
+            hover: Some(SpatialEguiHapticPulse::new(0.1, Duration::from_millis(10))),
+            press: Some(SpatialEguiHapticPulse::new(0.3, Duration::from_millis(20))),
+            grab: Some(SpatialEguiHapticPulse::new(0.5, Duration::from_millis(30))),
+            release: Some(SpatialEguiHapticPulse::new(0.2, Duration::from_millis(20))),
+        }
+    }
+}
+
+/// Records pulses instead of playing them, for tests and machines without a headset
+#[derive(Resource, Clone, Debug, Default)]
+pub struct SpatialEguiHapticsRecorder {
+    pub pulses: Vec<(Entity, SpatialEguiHapticPulse)>,
+}
+
+impl SpatialEguiHaptics for SpatialEguiHapticsRecorder {
+    fn pulse(&mut self, input_method: Entity, pulse: SpatialEguiHapticPulse) {
+        self.pulses.push((input_method, pulse));
+    }
+}
+
+fn send_haptics<B: SpatialEguiHaptics + Resource>(
+    settings: Res<SpatialEguiHapticsSettings>,
+    mut hovered: EventReader<SpatialEguiWindowHovered>,
+    mut pressed: EventReader<SpatialEguiWindowPressed>,
+    mut grabbed: EventReader<SpatialEguiWindowGrabbed>,
+    mut released: EventReader<SpatialEguiWindowReleased>,
+    methods: Query<
+        (),
+        Or<(
+            With<XrControllerInputMethodData>,
+            With<SimulatedSpatialPointer>,
+        )>,
+    >,
+    mut backend: ResMut<B>,
+) {
+    let pulses = hovered
+        .read()
+        .map(|e| (e.input_method, settings.hover))
+        .chain(pressed.read().map(|e| (e.input_method, settings.press)))
+        .chain(grabbed.read().map(|e| (e.input_method, settings.grab)))
+        .chain(released.read().map(|e| (e.input_method, settings.release)));
+    for (input_method, pulse) in pulses {
+        let Some(pulse) = pulse else {
+            continue;
+        };
+        if methods.contains(input_method) {
+            backend.pulse(input_method, pulse);
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use bevy::{ecs::entity::EntityHashSet, prelude::*, utils::HashMap};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    minimize::{MinimizeAnimation, MinimizedSpatialEguiWindow},
+    SpatialEguiWindowConfig,
+};
+
+/// Saves the layout of windows with a [`SpatialEguiLayoutId`] and restores it when they spawn.
+///
+/// With a `path` the layout is loaded from that RON file on startup and written back on exit.
+#[derive(Default)]
+pub struct SpatialEguiLayoutPlugin {
+    pub path: Option<PathBuf>,
+}
+
+impl Plugin for SpatialEguiLayoutPlugin {
+    fn build(&self, app: &mut App) {
+        let mut layout = self
+            .path
+            .as_deref()
+            .filter(|path| path.exists())
+            .map(|path| {
+                SpatialEguiLayout::load(path).unwrap_or_else(|err| {
+                    warn!("unable to load spatial egui layout from {path:?}: {err}");
+                    default()
+                })
+            })
+            .unwrap_or_default();
+        layout.path.clone_from(&self.path);
+        app.insert_resource(layout);
+        app.add_systems(
+            PostUpdate,
+            (restore_window_layouts, record_window_layouts)
+                .chain()
+                .before(TransformSystem::TransformPropagate),
+        );
+        app.add_systems(Last, save_layout_on_exit);
+    }
+}
+
+/// Stable id of a window or of an entity windows are parented to, used as the key in the layout
+#[derive(Component, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SpatialEguiLayoutId(pub String);
+
+/// The saved state of a single window
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SpatialEguiWindowLayout {
+    /// Relative to the parent
+    pub transform: Transform,
+    pub resolution: UVec2,
+    /// Physical height in meters
+    pub height: f32,
+    /// Layout id of the parent, `None` if the window has no parent or the parent has no id
+    pub parent: Option<String>,
+    pub visible: bool,
+}
+
+#[derive(Resource, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SpatialEguiLayout {
+    pub windows: HashMap<String, SpatialEguiWindowLayout>,
+    /// Named layouts that can be switched to with [`SpatialEguiLayout::apply_preset`]
+    pub presets: HashMap<String, HashMap<String, SpatialEguiWindowLayout>>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    #[serde(skip)]
+    restore_all: bool,
+    /// Windows waiting for their saved parent to spawn, they are restored once it does or their
+    /// entry is removed from `windows`
+    #[serde(skip)]
+    pending: EntityHashSet,
+}
+
+impl SpatialEguiLayout {
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_ron(&fs::read_to_string(path)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_ron()?)
+    }
+
+    pub fn from_ron(ron: &str) -> io::Result<Self> {
+        ron::from_str(ron).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    pub fn to_ron(&self) -> io::Result<String> {
+        ron::ser::to_string_pretty(self, default())
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// Stores the current layout under `name`, replacing an existing preset
+    pub fn save_preset(&mut self, name: impl Into<String>) {
+        self.presets.insert(name.into(), self.windows.clone());
+    }
+
+    /// Applies the preset `name` on top of the current layout and moves all windows to it, returns
+    /// `false` if there is no such preset
+    pub fn apply_preset(&mut self, name: &str) -> bool {
+        let Some(preset) = self.presets.get(name) else {
+            return false;
+        };
+        self.windows.extend(preset.clone());
+        self.restore_all = true;
+        true
+    }
+}
+
+fn restore_window_layouts(
+    mut layout: ResMut<SpatialEguiLayout>,
+    mut windows: Query<
+        (
+            Entity,
+            Ref<SpatialEguiLayoutId>,
+            &mut Transform,
+            &mut SpatialEguiWindowConfig,
+            &mut Visibility,
+            Option<&Parent>,
+        ),
+        // minimized windows keep their layout until they are restored
+        (
+            Without<MinimizedSpatialEguiWindow>,
+            Without<MinimizeAnimation>,
+        ),
+    >,
+    ids: Query<(Entity, &SpatialEguiLayoutId)>,
+    mut cmds: Commands,
+) {
+    let restore_all = layout.restore_all;
+    let layout = layout.bypass_change_detection();
+    layout.restore_all = false;
+    layout.pending.retain(|window| ids.contains(*window));
+    for (entity, id, mut transform, mut config, mut visibility, current_parent) in &mut windows {
+        if !(restore_all || id.is_added() || layout.pending.contains(&entity)) {
+            continue;
+        }
+        let Some(saved) = layout.windows.get(&id.0) else {
+            layout.pending.remove(&entity);
+            continue;
+        };
+        if config.resolution != saved.resolution || config.height != saved.height {
+            config.resolution = saved.resolution;
+            config.height = saved.height;
+        }
+        visibility.set_if_neq(if saved.visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        });
+        // the transform is relative to the saved parent, the hierarchy is left alone without it
+        match saved
+            .parent
+            .as_ref()
+            .and_then(|parent| ids.iter().find(|(_, id)| &id.0 == parent))
+        {
+            Some((parent, _)) => {
+                cmds.entity(entity).set_parent(parent);
+                *transform = saved.transform;
+                layout.pending.remove(&entity);
+            }
+            None if saved.parent.is_none() && current_parent.is_none() => {
+                *transform = saved.transform;
+            }
+            // the saved parent may spawn later
+            None if saved.parent.is_some() => {
+                layout.pending.insert(entity);
+            }
+            None => {}
+        }
+    }
+}
+
+fn record_window_layouts(
+    mut layout: ResMut<SpatialEguiLayout>,
+    windows: Query<
+        (
+            Entity,
+            &SpatialEguiLayoutId,
+            &Transform,
+            &SpatialEguiWindowConfig,
+            &Visibility,
+            Option<&Parent>,
+        ),
+        (
+            Or<(
+                Changed<Transform>,
+                Changed<SpatialEguiWindowConfig>,
+                Changed<Visibility>,
+                Changed<Parent>,
+            )>,
+            // minimizing shrinks and hides the window, its layout is the one from before
+            Without<MinimizedSpatialEguiWindow>,
+            Without<MinimizeAnimation>,
+        ),
+    >,
+    ids: Query<&SpatialEguiLayoutId>,
+) {
+    for (window, id, transform, config, visibility, parent) in &windows {
+        // the layout of windows waiting for their parent is still the saved one
+        if layout.pending.contains(&window) {
+            continue;
+        }
+        layout.windows.insert(
+            id.0.clone(),
+            SpatialEguiWindowLayout {
+                transform: *transform,
+                resolution: config.resolution,
+                height: config.height,
+                parent: parent
+                    .and_then(|parent| ids.get(parent.get()).ok())
+                    .map(|id| id.0.clone()),
+                visible: *visibility != Visibility::Hidden,
+            },
+        );
+    }
+}
+
+fn save_layout_on_exit(layout: Res<SpatialEguiLayout>, mut exit: EventReader<AppExit>) {
+    if exit.read().last().is_none() {
+        return;
+    }
+    let Some(path) = &layout.path else {
+        return;
+    };
+    if let Err(err) = layout.save(path) {
+        error!("unable to save spatial egui layout to {path:?}: {err}");
+    }
+}
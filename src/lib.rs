@@ -1,12 +1,31 @@
+pub mod bindings;
+pub mod chrome;
+pub mod cursor;
+pub mod events;
+pub mod grab;
+pub mod haptics;
+pub mod layout;
+pub mod material;
+pub mod minimize;
+pub mod placement;
+pub mod repaint;
+pub mod resize;
+pub mod resolution;
+pub mod simulated;
+pub mod snap;
+pub mod texture;
+pub mod touch;
+pub mod transparency;
+pub mod virtual_keyboard;
 pub mod window_mesh;
 
 use std::mem;
 
 use bevy::{
     color::palettes::css,
-    ecs::{entity::EntityHashMap, world::Command},
+    ecs::{entity::EntityHashMap, query::QueryData, world::Command},
     prelude::*,
-    render::render_resource::{Extent3d, TextureUsages},
+    render::render_resource::TextureUsages,
     window::PrimaryWindow,
 };
 use bevy_egui::{
@@ -18,31 +37,148 @@ use bevy_suis::{
     xr_controllers::XrControllerInputMethodData, CaptureContext, Field, InputHandler,
     InputHandlerCaptures, InputHandlingContext, PointerInputMethod,
 };
-use window_mesh::construct_window_mesh;
+use bindings::{
+    SpatialEguiControllerButton, SpatialEguiControllerFaceButtons, SpatialEguiHandGesture,
+    SpatialEguiInputBindings, SpatialEguiMouseButton,
+};
+use chrome::SpatialEguiChromeElement;
+use cursor::{SpatialEguiCursor, SpatialEguiCursorState, SpatialEguiWindowCursors};
+use events::{
+    SpatialEguiWindowFocused, SpatialEguiWindowGrabbed, SpatialEguiWindowHovered,
+    SpatialEguiWindowMoved, SpatialEguiWindowPressed, SpatialEguiWindowReleased,
+    SpatialEguiWindowSpawned, WindowEventWriters,
+};
+use grab::{GrabbedEguiWindow, SpatialEguiGrabSettings};
+use minimize::{MinimizedWindowState, SpatialEguiWindowOrb};
+use resize::{ResizingEguiWindow, SpatialEguiWindowResizable};
+use resolution::SpatialEguiAdaptiveResolution;
+use texture::SpatialEguiTextureSettings;
+use touch::SpatialEguiPrimaryPointerPolicy;
+use transparency::SpatialEguiTransparentWindow;
+use window_mesh::{window_uv, window_uv_to_local, SpatialEguiWindowShape, WindowMeshBuilder};
 
 pub struct SpatialEguiPlugin;
 
 impl Plugin for SpatialEguiPlugin {
     fn build(&self, app: &mut App) {
+        app.register_type::<SpatialEguiWindowConfig>();
+        app.init_resource::<cursor::SpatialEguiCursorSettings>();
+        app.init_resource::<SpatialEguiPrimaryPointerPolicy>();
+        app.init_resource::<SpatialEguiInputBindings>();
+        app.init_resource::<SpatialEguiFocus>();
+        app.init_resource::<minimize::SpatialEguiMinimizeSettings>();
+        events::add_window_events(app);
+        app.add_event::<chrome::SpatialEguiChromeButtonPressed>();
+        app.add_systems(
+            PostUpdate,
+            (
+                bevy_suis::pipe_input_ctx::<()>
+                    .pipe(read_suis_input)
+                    .pipe(simulated::add_simulated_input)
+                    .pipe(chrome::route_chrome_input)
+                    .pipe(minimize::route_orb_input)
+                    .pipe(add_idle_windows)
+                    .pipe(update_windows),
+                snap::update_snap_previews,
+                snap::apply_snapping,
+                apply_window_configs,
+                resolution::update_adaptive_resolution,
+                texture::apply_texture_settings,
+                transparency::hide_transparent_frames,
+                material::add_default_window_materials,
+                material::apply_window_materials::<StandardMaterial>,
+                (chrome::spawn_window_chrome, chrome::layout_window_chrome),
+                (
+                    minimize::minimize_windows,
+                    minimize::restore_windows,
+                    minimize::animate_minimize,
+                    minimize::despawn_stale_orbs,
+                )
+                    .chain(),
+                events::send_closed_events,
+                (cursor::draw_cursors, snap::draw_snap_previews)
+                    .run_if(resource_exists::<GizmoConfigStore>),
+            )
+                .chain(),
+        );
+        app.add_systems(
+            PostUpdate,
+            // placed from this frame's transforms, before they are propagated
+            (
+                placement::face_camera,
+                placement::lazy_follow,
+                placement::head_locked,
+            )
+                .before(TransformSystem::TransformPropagate),
+        );
         app.add_systems(
             PostUpdate,
-            bevy_suis::pipe_input_ctx::<()>.pipe(update_windows),
+            // sees the egui input and config changes of this frame
+            repaint::apply_repaint_policies
+                .after(EguiSet::ProcessOutput)
+                .after(events::send_closed_events),
         );
+        app.add_systems(Last, chrome::run_chrome_buttons);
+        repaint::build(app);
+        texture::build(app);
         app.add_systems(
             PreUpdate,
-            forward_egui_events
-                .after(EguiSet::ProcessInput)
-                .before(EguiSet::BeginFrame),
+            (
+                resolution::reset_pixels_per_point.before(EguiSet::InitContexts),
+                resolution::scale_render_targets
+                    .after(EguiSet::InitContexts)
+                    .before(EguiSet::ProcessInput),
+                forward_egui_events
+                    .after(EguiSet::ProcessInput)
+                    .before(EguiSet::BeginFrame),
+            ),
         );
     }
+
+    fn finish(&self, app: &mut App) {
+        texture::finish(app);
+    }
 }
 #[derive(Component, Clone, Copy, Debug)]
 pub struct ImmovableSpatialEguiWindow;
 
+/// Clicking the window doesn't move the keyboard focus to it
+#[derive(Component, Clone, Copy, Debug)]
+pub struct UnfocusableSpatialEguiWindow;
+
+/// The spatial window that receives keyboard input from the primary window, set to the last
+/// clicked window. The egui context of the primary window keeps its own focus and always receives
+/// its keyboard input.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SpatialEguiFocus {
+    pub window: Option<Entity>,
+}
+
+impl SpatialEguiFocus {
+    pub fn focus(&mut self, window: Entity) {
+        self.window = Some(window);
+    }
+
+    pub fn clear(&mut self) {
+        self.window = None;
+    }
+}
+
 fn forward_egui_events(
     mut query: Query<&mut EguiInput, With<SpatialEguiWindow>>,
     window_query: Query<&EguiInput, (With<PrimaryWindow>, Without<SpatialEguiWindow>)>,
+    focus: Res<SpatialEguiFocus>,
+    mut last_focused: Local<Option<Entity>>,
 ) {
+    if *last_focused != focus.window {
+        if let Some(mut egui_input) = last_focused.and_then(|e| query.get_mut(e).ok()) {
+            egui_input.events.push(egui::Event::WindowFocused(false));
+        }
+        if let Some(mut egui_input) = focus.window.and_then(|e| query.get_mut(e).ok()) {
+            egui_input.events.push(egui::Event::WindowFocused(true));
+        }
+        *last_focused = focus.window;
+    }
     let Ok(primary_input) = window_query.get_single() else {
         warn!("Unable to find one Primary Window!");
         return;
@@ -63,153 +199,534 @@ fn forward_egui_events(
         _ => None,
     });
 
-    for mut egui_input in query.iter_mut() {
-        egui_input.events.extend(events.clone());
+    if let Some(mut egui_input) = focus.window.and_then(|e| query.get_mut(e).ok()) {
+        egui_input.events.extend(events);
     }
 }
 
-#[derive(Clone, Copy, Component)]
-struct GrabbedEguiWindow {
-    method_relative_transform: Transform,
+/// The local transform that places an entity with `parent` at `world`
+pub(crate) fn parent_relative_transform(
+    world: &GlobalTransform,
+    parent: Option<&GlobalTransform>,
+) -> Transform {
+    match parent {
+        Some(parent) => world.reparented_to(parent),
+        None => world.compute_transform(),
+    }
 }
 
-fn update_windows(
+#[derive(QueryData)]
+#[query_data(mutable)]
+struct WindowQuery {
+    window: &'static mut SpatialEguiWindow,
+    phys_size: &'static SpatialEguiWindowPhysicalSize,
+    config: &'static mut SpatialEguiWindowConfig,
+    egui_input: &'static mut EguiInput,
+    egui_ctx: &'static mut EguiContext,
+    texture_handle: Option<&'static EguiRenderToTextureHandle>,
+    grabbed: Option<&'static mut GrabbedEguiWindow>,
+    grab_settings: Option<&'static SpatialEguiGrabSettings>,
+    resizing: Option<&'static ResizingEguiWindow>,
+    resizable: Option<&'static SpatialEguiWindowResizable>,
+    transform: &'static mut Transform,
+    parent: Option<&'static Parent>,
+    immovable: Has<ImmovableSpatialEguiWindow>,
+    cursors: Option<&'static mut SpatialEguiWindowCursors>,
+    unfocusable: Has<UnfocusableSpatialEguiWindow>,
+    visibility: &'static InheritedVisibility,
+}
+
+/// Input of a window for this frame, from bevy_suis or simulated pointers
+pub(crate) struct WindowInput {
+    pub(crate) handler: Entity,
+    pub(crate) handler_location: GlobalTransform,
+    /// Whether any input method is captured by the window
+    pub(crate) captured: bool,
+    pub(crate) methods: Vec<MethodInput>,
+}
+
+pub(crate) struct MethodInput {
+    pub(crate) input_method: Entity,
+    /// In window space
+    pub(crate) closest_point: Vec3,
+    /// In window space
+    pub(crate) input_method_location: Transform,
+    pub(crate) method_gt: GlobalTransform,
+    pub(crate) is_pointer: bool,
+    pub(crate) state: InputState,
+    pub(crate) target: MethodTarget,
+}
+
+/// What part of the window an input method interacts with
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum MethodTarget {
+    /// The egui surface
+    Content,
+    /// Window chrome that moves the window
+    Move,
+    /// Window chrome that resizes the given edges of the window
+    Resize(Vec2),
+}
+
+/// The input bindings of a window, chrome elements and the orb of a minimized window use the ones
+/// of their window
+fn input_bindings<'a>(
+    handler: Entity,
+    owners: &Query<(
+        Option<&SpatialEguiChromeElement>,
+        Option<&SpatialEguiWindowOrb>,
+    )>,
+    window_bindings: &'a Query<&SpatialEguiInputBindings>,
+    global_bindings: &'a SpatialEguiInputBindings,
+) -> &'a SpatialEguiInputBindings {
+    let window = match owners.get(handler) {
+        Ok((Some(element), _)) => element.window,
+        Ok((_, Some(orb))) => orb.window,
+        _ => handler,
+    };
+    window_bindings.get(window).unwrap_or(global_bindings)
+}
+
+fn read_suis_input(
     ctxs: In<Vec<InputHandlingContext>>,
-    images: Res<Assets<Image>>,
-    mut windows: Query<
-        (
-            &InputHandlerCaptures,
-            &SpatialEguiWindowPhysicalSize,
-            &mut EguiInput,
-            &mut EguiContext,
-            &EguiRenderToTextureHandle,
-            Option<&mut GrabbedEguiWindow>,
-            &mut Transform,
-            Option<&Parent>,
-            Has<ImmovableSpatialEguiWindow>,
-        ),
-        With<SpatialEguiWindow>,
+    windows: Query<
+        (&InputHandlerCaptures, Option<&SpatialEguiWindow>),
+        Or<(
+            With<SpatialEguiWindow>,
+            With<SpatialEguiChromeElement>,
+            With<SpatialEguiWindowOrb>,
+        )>,
     >,
     methods: Query<(
         &GlobalTransform,
         Option<&XrControllerInputMethodData>,
+        Option<&SpatialEguiControllerFaceButtons>,
         Option<&HandInputMethodData>,
         Option<&MouseInputMethodData>,
         Has<PointerInputMethod>,
     )>,
+    owners: Query<(
+        Option<&SpatialEguiChromeElement>,
+        Option<&SpatialEguiWindowOrb>,
+    )>,
+    window_bindings: Query<&SpatialEguiInputBindings>,
+    global_bindings: Res<SpatialEguiInputBindings>,
+    time: Res<Time>,
+) -> Vec<WindowInput> {
+    ctxs.iter()
+        .filter_map(|ctx| {
+            let (handler, window) = windows.get(ctx.handler).ok()?;
+            let bindings = input_bindings(ctx.handler, &owners, &window_bindings, &global_bindings);
+            let methods = ctx
+                .methods
+                .iter()
+                .filter_map(|ctx| methods.get(ctx.input_method).map(|v| (ctx, v)).ok())
+                .map(
+                    |(
+                        method_ctx,
+                        (
+                            method_gt,
+                            xr_controller_data,
+                            face_buttons,
+                            xr_hand_data,
+                            mouse_data,
+                            is_pointer,
+                        ),
+                    )| {
+                        let mut current_state = InputState::default();
+                        let closest_point = method_ctx.closest_point;
+                        let location = method_ctx.input_method_location.translation;
+                        let touched = match window {
+                            Some(window) => window.is_touched(closest_point, location),
+                            None => closest_point.distance(location) <= f32::EPSILON,
+                        };
+                        if touched && !is_pointer {
+                            current_state.click = true;
+                        }
+                        if let Some(controller) = xr_controller_data {
+                            let pressed = |button: Option<SpatialEguiControllerButton>| {
+                                button
+                                    .is_some_and(|button| button.pressed(controller, face_buttons))
+                            };
+                            current_state.click |= pressed(bindings.controller_click);
+                            current_state.grab |= pressed(bindings.controller_grab);
+                            current_state.secondary |= pressed(bindings.controller_secondary);
+                            current_state.middle |= pressed(bindings.controller_middle);
+                            current_state.continuous_scroll +=
+                                bindings.stick_scroll(controller.stick_pos, time.delta_seconds());
+                        }
+                        if let Some(hand) = xr_hand_data {
+                            let active = |gesture: Option<SpatialEguiHandGesture>| {
+                                gesture.is_some_and(|gesture| {
+                                    gesture.active(hand, &ctx.handler_location)
+                                })
+                            };
+                            current_state.click |= active(bindings.hand_click);
+                            current_state.secondary |= active(bindings.hand_secondary);
+                            current_state.middle |= active(bindings.hand_middle);
+                        }
+                        if let Some(mouse) = mouse_data {
+                            let pressed = |button: Option<SpatialEguiMouseButton>| {
+                                button.is_some_and(|button| button.pressed(mouse))
+                            };
+                            current_state.click |= pressed(bindings.mouse_click);
+                            current_state.grab |= pressed(bindings.mouse_grab);
+                            current_state.secondary |= pressed(bindings.mouse_secondary);
+                            current_state.middle |= pressed(bindings.mouse_middle);
+                            current_state.discrete_scroll +=
+                                mouse.discrete_scroll * bindings.mouse_scroll_sensitivity;
+                            current_state.continuous_scroll +=
+                                mouse.continuous_scroll * bindings.mouse_scroll_sensitivity;
+                        }
+                        MethodInput {
+                            input_method: method_ctx.input_method,
+                            closest_point: method_ctx.closest_point,
+                            input_method_location: method_ctx.input_method_location,
+                            method_gt: *method_gt,
+                            is_pointer,
+                            state: current_state,
+                            target: MethodTarget::Content,
+                        }
+                    },
+                )
+                .collect();
+            Some(WindowInput {
+                handler: ctx.handler,
+                handler_location: ctx.handler_location,
+                captured: !handler.captured_methods.is_empty(),
+                methods,
+            })
+        })
+        .collect()
+}
+
+/// Windows without any input still have to release held buttons and lose the egui pointer
+fn add_idle_windows(
+    In(mut inputs): In<Vec<WindowInput>>,
+    windows: Query<(Entity, &GlobalTransform), With<SpatialEguiWindow>>,
+) -> Vec<WindowInput> {
+    for (entity, window_gt) in &windows {
+        if !inputs.iter().any(|input| input.handler == entity) {
+            inputs.push(WindowInput {
+                handler: entity,
+                handler_location: *window_gt,
+                captured: false,
+                methods: Vec::new(),
+            });
+        }
+    }
+    inputs
+}
+
+fn update_windows(
+    inputs: In<Vec<WindowInput>>,
+    images: Res<Assets<Image>>,
+    mut windows: Query<WindowQuery, With<SpatialEguiWindow>>,
     mut state: Local<EntityHashMap<EntityHashMap<InputState>>>,
+    mut primary_methods: Local<EntityHashMap<Entity>>,
+    primary_policy: Res<SpatialEguiPrimaryPointerPolicy>,
+    mut focus: ResMut<SpatialEguiFocus>,
+    mut events: WindowEventWriters,
     gt_query: Query<&GlobalTransform>,
     time: Res<Time>,
     mut cmds: Commands,
 ) {
-    for ctx in ctxs.iter() {
-        let Ok((
-            handler,
+    for ctx in inputs.0 {
+        let Ok(WindowQueryItem {
+            mut window,
             phys_size,
+            mut config,
             mut egui_input,
             mut egui_ctx,
             texture_handle,
             mut grabbed,
-            mut window_transform,
+            grab_settings,
+            resizing,
+            resizable,
+            transform: mut window_transform,
             parent,
             immovable,
-        )) = windows.get_mut(ctx.handler)
+            mut cursors,
+            unfocusable,
+            visibility,
+        }) = windows.get_mut(ctx.handler)
         else {
             continue;
         };
-        if handler.captured_methods.is_empty() {
+        if !ctx.captured {
             egui_input.events.push(egui::Event::PointerGone);
         }
-        let resolution = images.get(&texture_handle.0).unwrap().size_f32();
-        let mut next_states = EntityHashMap::<InputState>::default();
-        for (method_ctx, (method_gt, xr_controller_data, xr_hand_data, mouse_data, is_pointer)) in
-            ctx.methods
+        // the texture is added by apply_window_configs
+        let Some(texture) = texture_handle.map(|texture| &texture.0) else {
+            continue;
+        };
+        let egui_size =
+            images.get(texture).unwrap().size_f32() / egui_ctx.get_mut().pixels_per_point();
+        let egui_size = egui::vec2(egui_size.x, egui_size.y);
+        if window.egui_size != egui_size {
+            window.egui_size = egui_size;
+        }
+        if let Some(cursors) = cursors.as_mut() {
+            cursors.0.clear();
+        }
+        let mut last_states = mem::take(state.entry(ctx.handler).or_default());
+        let frame_methods = ctx
+            .methods
+            .into_iter()
+            // hidden windows don't take input
+            .filter(|_| visibility.get())
+            .map(|method| {
+                let last_state = last_states.remove(&method.input_method);
+                if last_state.is_none() {
+                    events.hovered.send(SpatialEguiWindowHovered {
+                        window: ctx.handler,
+                        input_method: method.input_method,
+                    });
+                }
+                (method, last_state.unwrap_or_default())
+            })
+            .collect::<Vec<_>>();
+        let last_primary = primary_methods.get(&ctx.handler).copied();
+        let primary = primary_policy.select(
+            last_primary,
+            &frame_methods
                 .iter()
-                .filter_map(|ctx| methods.get(ctx.input_method).map(|v| (ctx, v)).ok())
-        {
-            let mut current_state = InputState::default();
-            if method_ctx
-                .closest_point
-                .distance(method_ctx.input_method_location.translation)
-                <= f32::EPSILON
-                && !is_pointer
+                .map(|(method, last_state)| {
+                    (method.input_method, method.state.click, last_state.click)
+                })
+                .collect::<Vec<_>>(),
+        );
+        match primary {
+            Some(primary) => primary_methods.insert(ctx.handler, primary),
+            None => primary_methods.remove(&ctx.handler),
+        };
+        let grab_settings = grab_settings.copied().unwrap_or_default();
+        let grabbing_methods = frame_methods
+            .iter()
+            .filter(|(method, _)| method.state.grab)
+            .map(|(method, _)| (method.input_method, method.method_gt))
+            .collect::<EntityHashMap<_>>();
+        let mut next_states = EntityHashMap::<InputState>::default();
+        for (method, last_state) in frame_methods {
+            let MethodInput {
+                input_method,
+                closest_point,
+                input_method_location,
+                method_gt,
+                is_pointer,
+                state: mut current_state,
+                target,
+            } = method;
+            let closest_point = window.shape.hit(
+                closest_point,
+                &input_method_location,
+                is_pointer,
+                window.size.z,
+            );
+            let surface_point = window.unroll(closest_point);
+            if (!current_state.grab)
+                && last_state.grab
+                && release_grab(
+                    ctx.handler,
+                    input_method,
+                    grabbed.as_deref_mut(),
+                    resizing,
+                    &grabbing_methods,
+                    &ctx.handler_location,
+                    &mut cmds,
+                )
             {
-                current_state.click = true;
-            }
-            if let Some(controller) = xr_controller_data {
-                current_state.click |= controller.trigger_pulled;
-                current_state.grab |= controller.squeezed;
-                current_state.continuous_scroll +=
-                    controller.stick_pos * time.delta_seconds() * 1000.;
-            }
-            if let Some(hand) = xr_hand_data {
-                let hand = hand.get_in_relative_space(&ctx.handler_location);
-                current_state.click |= hand.index.tip.pos.distance(hand.thumb.tip.pos)
-                    > (0.002 + hand.index.tip.radius + hand.thumb.tip.radius)
-            }
-            if let Some(mouse) = mouse_data {
-                current_state.click |= mouse.left_button.pressed;
-                current_state.grab |= mouse.right_button.pressed;
-                current_state.discrete_scroll += mouse.discrete_scroll;
-                current_state.continuous_scroll += mouse.continuous_scroll;
+                events.released.send(SpatialEguiWindowReleased {
+                    window: ctx.handler,
+                    input_method,
+                });
             }
-            let last_state = state
-                .entry(ctx.handler)
-                .or_default()
-                .remove(&method_ctx.input_method)
-                .unwrap_or_default();
-            if (!current_state.grab) && last_state.grab {
-                cmds.entity(ctx.handler).remove::<GrabbedEguiWindow>();
+            if current_state.grab && (!last_state.grab) && grab_settings.two_handed_scale {
+                if let Some(grabbed) = grabbed.as_mut() {
+                    if grabbed.add_second_method(input_method, &grabbing_methods, config.height) {
+                        events.grabbed.send(SpatialEguiWindowGrabbed {
+                            window: ctx.handler,
+                            input_method,
+                        });
+                    }
+                }
             }
-            if current_state.grab && (!last_state.grab) && !immovable {
-                cmds.entity(ctx.handler).insert(GrabbedEguiWindow {
-                    method_relative_transform: Transform::from_matrix(
-                        method_gt.compute_matrix().inverse()
-                            * ctx.handler_location.compute_matrix(),
-                    ),
+            if current_state.grab
+                && (!last_state.grab)
+                && grabbed.is_none()
+                && resizing.is_none()
+                && !immovable
+            {
+                let resize = match target {
+                    MethodTarget::Content => resizable.and_then(|resizable| {
+                        ResizingEguiWindow::try_start(
+                            input_method,
+                            resizable,
+                            config.pixels_per_meter(),
+                            window.shape,
+                            phys_size.0,
+                            surface_point,
+                            *window_transform,
+                            ctx.handler_location,
+                        )
+                    }),
+                    MethodTarget::Move => None,
+                    MethodTarget::Resize(edges) => Some(ResizingEguiWindow::new(
+                        input_method,
+                        edges,
+                        config.pixels_per_meter(),
+                        window.shape,
+                        phys_size.0,
+                        surface_point,
+                        *window_transform,
+                        ctx.handler_location,
+                    )),
+                };
+                match resize {
+                    Some(resize) => {
+                        cmds.entity(ctx.handler).insert(resize);
+                    }
+                    None => {
+                        cmds.entity(ctx.handler).insert(GrabbedEguiWindow::new(
+                            input_method,
+                            &method_gt,
+                            &ctx.handler_location,
+                        ));
+                    }
+                }
+                events.grabbed.send(SpatialEguiWindowGrabbed {
+                    window: ctx.handler,
+                    input_method,
                 });
             }
-            if let Some(grabbed) = grabbed.as_mut() {
-                let offset_matrix = parent
-                    .and_then(|e| gt_query.get(e.get()).ok())
-                    .unwrap_or(&GlobalTransform::IDENTITY);
-
-                grabbed.method_relative_transform.translation.z +=
-                    current_state.continuous_scroll.y / 500.0;
-                grabbed.method_relative_transform.translation.z +=
-                    current_state.discrete_scroll.y / 10.0;
-
-                *window_transform = Transform::from_matrix(
-                    method_gt
-                        .mul_transform(grabbed.method_relative_transform)
-                        .compute_matrix()
-                        * offset_matrix.compute_matrix().inverse(),
+            if let Some(resizing) =
+                resizing.filter(|resizing| resizing.input_method == input_method)
+            {
+                if current_state.grab {
+                    // resize chrome also works on windows that aren't resizable by their edges
+                    let resizable = resizable.copied().unwrap_or_default();
+                    let (size, transform) = resizing.update(
+                        &resizable,
+                        &input_method_location,
+                        is_pointer,
+                        &ctx.handler_location,
+                    );
+                    if size != phys_size.0.xy() {
+                        config.resolution = resizing.resolution(size);
+                        config.height = size.y;
+                        *window_transform = transform;
+                        events.moved.send(SpatialEguiWindowMoved {
+                            window: ctx.handler,
+                            input_method,
+                            transform,
+                        });
+                    }
+                }
+            }
+            if let Some(grabbed) = grabbed
+                .as_mut()
+                .filter(|grabbed| grabbed.input_method == input_method)
+            {
+                grabbed.push(
+                    (current_state.continuous_scroll.y / 500.0)
+                        + (current_state.discrete_scroll.y / 10.0),
                 );
+                let aspect_ratio = window.size.x / window.size.y;
+                if let Some(height) =
+                    grabbed.two_handed_height(&grabbing_methods, resizable, aspect_ratio)
+                {
+                    if config.height != height {
+                        config.height = height;
+                    }
+                }
+                let viewer = grab_settings
+                    .face_viewer
+                    .and_then(|e| gt_query.get(e).ok())
+                    .map(GlobalTransform::translation);
+                let last_transform = *window_transform;
+                placement::move_window(
+                    &mut window_transform,
+                    &ctx.handler_location,
+                    parent.and_then(|e| gt_query.get(e.get()).ok()),
+                    grab_settings.target(grabbed.target(&method_gt), viewer),
+                    placement::damping_factor(grab_settings.smoothing, time.delta_seconds()),
+                );
+                // a window held still isn't moved, recomposing its transform adds float noise
+                let moved = !window_transform
+                    .translation
+                    .abs_diff_eq(last_transform.translation, 1e-5)
+                    || !window_transform
+                        .rotation
+                        .abs_diff_eq(last_transform.rotation, 1e-5);
+                if moved {
+                    events.moved.send(SpatialEguiWindowMoved {
+                        window: ctx.handler,
+                        input_method,
+                        transform: *window_transform,
+                    });
+                }
             }
-            if grabbed.is_none() {
-                let uv = ((method_ctx.closest_point.xy() / phys_size.0.xy()) * -1.) + 0.5;
-                let pos = egui::Pos2 {
-                    x: (uv.x * resolution.x) / egui_ctx.get_mut().pixels_per_point(),
-                    y: (uv.y * resolution.y) / egui_ctx.get_mut().pixels_per_point(),
-                };
-                egui_input.events.push(egui::Event::PointerMoved(pos));
+            let egui_pos = window
+                .local_to_egui(surface_point)
+                .filter(|_| grabbed.is_none() && resizing.is_none())
+                .filter(|_| target == MethodTarget::Content);
+            if let Some(pos) = egui_pos {
+                current_state.pos = pos;
                 if current_state.click && !last_state.click {
-                    egui_input.events.push(egui::Event::PointerButton {
-                        pos,
-                        button: egui::PointerButton::Primary,
-                        pressed: true,
-                        modifiers: egui::Modifiers::NONE,
+                    events.pressed.send(SpatialEguiWindowPressed {
+                        window: ctx.handler,
+                        input_method,
                     });
+                    if !unfocusable
+                        && focus.set_if_neq(SpatialEguiFocus {
+                            window: Some(ctx.handler),
+                        })
+                    {
+                        events.focused.send(SpatialEguiWindowFocused {
+                            window: ctx.handler,
+                            input_method,
+                        });
+                    }
                 }
-                if !current_state.click && last_state.click {
-                    egui_input.events.push(egui::Event::PointerButton {
+                let touch_phase = match (current_state.click, last_state.click) {
+                    (true, false) => Some(egui::TouchPhase::Start),
+                    (true, true) => Some(egui::TouchPhase::Move),
+                    (false, true) => Some(egui::TouchPhase::End),
+                    (false, false) => None,
+                };
+                if let Some(phase) = touch_phase {
+                    egui_input.events.push(egui::Event::Touch {
+                        device_id: touch::touch_device_id(ctx.handler),
+                        id: touch::touch_id(input_method),
+                        phase,
                         pos,
-                        button: egui::PointerButton::Primary,
-                        pressed: false,
-                        modifiers: egui::Modifiers::NONE,
+                        force: None,
                     });
                 }
-                if current_state.discrete_scroll != Vec2::ZERO {
+                // only the primary method drives the pointer, widgets that don't understand
+                // touches would otherwise see pointers from different methods fighting
+                let is_primary = primary == Some(input_method);
+                let was_primary = last_primary == Some(input_method);
+                if is_primary {
+                    egui_input.events.push(egui::Event::PointerMoved(pos));
+                }
+                for ((button, pressed), (_, was_pressed)) in current_state
+                    .pointer_buttons()
+                    .into_iter()
+                    .zip(last_state.pointer_buttons())
+                {
+                    let pressed = pressed && is_primary;
+                    let was_pressed = was_pressed && was_primary;
+                    if pressed != was_pressed {
+                        egui_input.events.push(egui::Event::PointerButton {
+                            pos,
+                            button,
+                            pressed,
+                            modifiers: egui::Modifiers::NONE,
+                        });
+                    }
+                }
+                if is_primary && current_state.discrete_scroll != Vec2::ZERO {
                     egui_input.events.push(egui::Event::MouseWheel {
                         unit: egui::MouseWheelUnit::Line,
                         delta: egui::Vec2 {
@@ -219,7 +736,7 @@ fn update_windows(
                         modifiers: egui::Modifiers::NONE,
                     });
                 }
-                if current_state.continuous_scroll != Vec2::ZERO {
+                if is_primary && current_state.continuous_scroll != Vec2::ZERO {
                     egui_input.events.push(egui::Event::MouseWheel {
                         unit: egui::MouseWheelUnit::Point,
                         delta: egui::Vec2 {
@@ -230,33 +747,117 @@ fn update_windows(
                     });
                 }
             }
-            next_states.insert(method_ctx.input_method, current_state);
+            if let Some(cursors) = cursors.as_mut() {
+                let state = if current_state.grab || grabbed.is_some() || resizing.is_some() {
+                    SpatialEguiCursorState::Grab
+                } else if current_state.click {
+                    SpatialEguiCursorState::Press
+                } else {
+                    SpatialEguiCursorState::Hover
+                };
+                cursors.0.push(SpatialEguiCursor {
+                    input_method,
+                    position: closest_point,
+                    state,
+                });
+            }
+            next_states.insert(input_method, current_state);
         }
-        for state in mem::replace(state.entry(ctx.handler).or_default(), next_states).into_values()
-        {
+        // methods that stopped interacting with the window
+        for (method, state) in last_states {
+            if last_primary == Some(method) {
+                for (button, pressed) in state.pointer_buttons() {
+                    if pressed {
+                        egui_input.events.push(egui::Event::PointerButton {
+                            pos: state.pos,
+                            button,
+                            pressed: false,
+                            modifiers: egui::Modifiers::NONE,
+                        });
+                    }
+                }
+            }
             if state.click {
-                egui_input.events.push(egui::Event::PointerButton {
-                    pos: Pos2::ZERO,
-                    button: egui::PointerButton::Primary,
-                    pressed: false,
-                    modifiers: egui::Modifiers::NONE,
+                egui_input.events.push(egui::Event::Touch {
+                    device_id: touch::touch_device_id(ctx.handler),
+                    id: touch::touch_id(method),
+                    phase: egui::TouchPhase::Cancel,
+                    pos: state.pos,
+                    force: None,
                 });
             }
-            if state.grab {
-                cmds.entity(ctx.handler).remove::<GrabbedEguiWindow>();
+            if state.grab
+                && release_grab(
+                    ctx.handler,
+                    method,
+                    grabbed.as_deref_mut(),
+                    resizing,
+                    &grabbing_methods,
+                    &ctx.handler_location,
+                    &mut cmds,
+                )
+            {
+                events.released.send(SpatialEguiWindowReleased {
+                    window: ctx.handler,
+                    input_method: method,
+                });
+            }
+        }
+        state.insert(ctx.handler, next_states);
+    }
+}
+
+/// Lets go of the window with `input_method`, returns whether it was moving or resizing the window
+fn release_grab(
+    window: Entity,
+    input_method: Entity,
+    grabbed: Option<&mut GrabbedEguiWindow>,
+    resizing: Option<&ResizingEguiWindow>,
+    grabbing_methods: &EntityHashMap<GlobalTransform>,
+    window_gt: &GlobalTransform,
+    cmds: &mut Commands,
+) -> bool {
+    match grabbed {
+        // only one of the methods holding the window counts
+        Some(grabbed) if !grabbed.holds(input_method) => false,
+        Some(grabbed) => {
+            if !grabbed.release(input_method, grabbing_methods, window_gt) {
+                cmds.entity(window).remove::<GrabbedEguiWindow>();
             }
+            true
         }
+        None => match resizing {
+            Some(resizing) if resizing.input_method == input_method => {
+                cmds.entity(window).remove::<ResizingEguiWindow>();
+                true
+            }
+            _ => false,
+        },
     }
 }
 
 #[derive(Default)]
-struct InputState {
-    click: bool,
-    grab: bool,
+pub(crate) struct InputState {
+    pub(crate) click: bool,
+    pub(crate) grab: bool,
+    pub(crate) secondary: bool,
+    pub(crate) middle: bool,
     /// How many Lines to scroll
-    discrete_scroll: Vec2,
+    pub(crate) discrete_scroll: Vec2,
     /// How many Pixels to scroll
-    continuous_scroll: Vec2,
+    pub(crate) continuous_scroll: Vec2,
+    /// Last egui position of the method on the window
+    pub(crate) pos: Pos2,
+}
+
+impl InputState {
+    fn pointer_buttons(&self) -> [(egui::PointerButton, bool); 3] {
+        [
+            (egui::PointerButton::Primary, self.click),
+            (egui::PointerButton::Secondary, self.secondary),
+            (egui::PointerButton::Middle, self.middle),
+        ]
+    }
 }
 
 pub struct SpawnSpatialEguiWindowCommand {
@@ -266,65 +867,303 @@ pub struct SpawnSpatialEguiWindowCommand {
     pub resolution: UVec2,
     pub unlit: bool,
     pub height: f32,
+    /// Spawns a [`SpatialEguiTransparentWindow`] that only shows its egui content
+    pub transparent: bool,
+    /// Spawns a [`SpatialEguiWindowResizable`] so the window can be resized from its edges
+    pub resizable: bool,
 }
 
+/// A spatial window, egui is shown on its -Z face
 #[derive(Clone, Copy, Debug, Component)]
-pub struct SpatialEguiWindow;
+pub struct SpatialEguiWindow {
+    /// Physical size in meters
+    pub size: Vec3,
+    /// Size of the egui screen in points
+    pub egui_size: egui::Vec2,
+    pub shape: SpatialEguiWindowShape,
+}
+
+impl SpatialEguiWindow {
+    /// Maps a point in window space onto the unrolled egui side of the window, where
+    /// [`SpatialEguiWindow::local_to_egui`] takes it
+    pub fn unroll(&self, pos: Vec3) -> Vec2 {
+        self.shape.unroll(pos, self.size.z)
+    }
+
+    /// Maps a point on the unrolled egui side to egui, `None` if it's outside of the window
+    pub fn local_to_egui(&self, pos: Vec2) -> Option<Pos2> {
+        let uv = window_uv(pos, self.size.xy());
+        if !(0.0..=1.0).contains(&uv.x) || !(0.0..=1.0).contains(&uv.y) {
+            return None;
+        }
+        Some(Pos2::new(uv.x * self.egui_size.x, uv.y * self.egui_size.y))
+    }
+
+    /// Maps a point in egui to window space, on the surface of the -Z face
+    pub fn egui_to_local(&self, pos: Pos2) -> Vec3 {
+        let uv = Vec2::new(pos.x / self.egui_size.x, pos.y / self.egui_size.y);
+        self.shape
+            .roll(window_uv_to_local(uv, self.size.xy()), self.size.z)
+    }
+
+    /// Projects a world space point onto the window and maps it to egui, `None` if it's outside
+    /// of the window
+    pub fn world_to_egui(&self, window_gt: &GlobalTransform, pos: Vec3) -> Option<Pos2> {
+        let local_pos = window_gt.affine().inverse().transform_point3(pos);
+        self.local_to_egui(self.unroll(local_pos))
+    }
+
+    /// Maps a point in egui to world space, on the surface of the -Z face
+    pub fn egui_to_world(&self, window_gt: &GlobalTransform, pos: Pos2) -> Vec3 {
+        window_gt.transform_point(self.egui_to_local(pos))
+    }
+
+    /// How far a point in window space is in front of the egui side, negative behind it
+    pub fn surface_distance(&self, pos: Vec3) -> f32 {
+        self.shape.surface_distance(pos, self.size.z)
+    }
+
+    /// Whether a near input method at `location` touches the egui side or is in the window behind
+    /// it, `closest_point` is the closest point of the window field. The field of curved windows
+    /// also covers the space in front of their surface.
+    pub(crate) fn is_touched(&self, closest_point: Vec3, location: Vec3) -> bool {
+        closest_point.distance(location) <= f32::EPSILON
+            && self.surface_distance(location) <= f32::EPSILON
+    }
+}
+
+impl Default for SpawnSpatialEguiWindowCommand {
+    /// A window like [`SpatialEguiWindowConfig::default`] at the origin
+    fn default() -> Self {
+        let config = SpatialEguiWindowConfig::default();
+        Self {
+            target_entity: None,
+            position: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+            resolution: config.resolution,
+            unlit: config.unlit,
+            height: config.height,
+            transparent: false,
+            resizable: false,
+        }
+    }
+}
 
 impl Command for SpawnSpatialEguiWindowCommand {
     fn apply(self, world: &mut World) {
-        let mut textures = world.resource_mut::<Assets<Image>>();
-        let texture = textures.add({
-            let size = Extent3d {
-                width: self.resolution.x,
-                height: self.resolution.y,
-                depth_or_array_layers: 1,
-            };
-            let mut output_texture = Image {
-                data: vec![0; (size.width * size.height * 4) as usize],
+        let bundle = (
+            SpatialEguiWindowConfig {
+                resolution: self.resolution,
+                height: self.height,
+                unlit: self.unlit,
                 ..default()
-            };
-            output_texture.texture_descriptor.usage |= TextureUsages::RENDER_ATTACHMENT;
-            output_texture.texture_descriptor.size = size;
-            output_texture
-        });
-        let mut materials = world.remove_resource::<Assets<StandardMaterial>>().unwrap();
-        let mut meshes = world.remove_resource::<Assets<Mesh>>().unwrap();
-        let size = Vec3::new(
-            self.height * (self.resolution.y as f32 / (self.resolution.x as f32)),
-            self.height,
-            0.05,
+            },
+            Transform::from_translation(self.position).with_rotation(self.rotation),
         );
-        let mat = materials.add(StandardMaterial {
-            base_color: Color::WHITE,
-            base_color_texture: Some(texture.clone()),
-            unlit: self.unlit,
-            ..Default::default()
-        });
-        let mesh = meshes.add(construct_window_mesh(size.xy(), size.z));
-        let bundle = (
-            Field::Cuboid(Cuboid::from_size(size)),
-            InputHandler::new(input_surface_capture_condition),
-            EguiRenderToTextureHandle(texture),
-            PbrBundle {
-                mesh,
-                material: mat,
-                transform: Transform::from_translation(self.position).with_rotation(self.rotation),
-                ..Default::default()
+        let mut window = match self.target_entity {
+            Some(target) => world.entity_mut(target),
+            None => world.spawn_empty(),
+        };
+        window.insert(bundle);
+        if self.transparent {
+            window.insert(SpatialEguiTransparentWindow::default());
+        }
+        if self.resizable {
+            window.insert(SpatialEguiWindowResizable::default());
+        }
+    }
+}
+
+/// Turns an entity into a spatial window, changing it reconfigures the window
+#[derive(Clone, Copy, Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct SpatialEguiWindowConfig {
+    /// Resolution of the texture egui renders into
+    pub resolution: UVec2,
+    /// Physical height in meters, the width follows the aspect ratio of `resolution`
+    pub height: f32,
+    pub unlit: bool,
+    /// Physical depth in meters
+    pub depth: f32,
+    /// The arc of a curved window follows from its width, the `arc` set here is ignored, see
+    /// [`SpatialEguiWindowConfig::surface_shape`]
+    pub shape: SpatialEguiWindowShape,
+    /// Radius of the rounded corners in meters, 0.0 for square corners
+    pub corner_radius: f32,
+    /// How many segments each rounded corner is made of
+    pub corner_segments: u32,
+    /// Color of the back and edges of the window
+    pub frame_color: Color,
+}
+
+impl Default for SpatialEguiWindowConfig {
+    fn default() -> Self {
+        Self {
+            resolution: UVec2::splat(512),
+            height: 1.0,
+            unlit: true,
+            depth: 0.05,
+            shape: SpatialEguiWindowShape::Flat,
+            corner_radius: 0.0,
+            corner_segments: 8,
+            frame_color: css::DIM_GRAY.into(),
+        }
+    }
+}
+
+impl SpatialEguiWindowConfig {
+    /// Size of the window in meters, curved windows are measured along their surface
+    pub fn physical_size(&self) -> Vec3 {
+        let width = self.height * (self.resolution.x as f32 / self.resolution.y as f32);
+        Vec3::new(width, self.height, self.depth)
+    }
+
+    /// The shape of the egui side, curved windows span the arc their width covers
+    pub fn surface_shape(&self) -> SpatialEguiWindowShape {
+        match self.shape {
+            SpatialEguiWindowShape::Flat => SpatialEguiWindowShape::Flat,
+            SpatialEguiWindowShape::Cylinder { radius, .. } => SpatialEguiWindowShape::Cylinder {
+                radius,
+                arc: self.physical_size().x / radius,
             },
-            SpatialEguiWindow,
+        }
+    }
+
+    pub fn mesh_builder(&self) -> WindowMeshBuilder {
+        let size = self.physical_size();
+        WindowMeshBuilder::new(size.xy(), size.z)
+            .with_shape(self.surface_shape())
+            .with_rounded_corners(self.corner_radius, self.corner_segments)
+    }
+
+    pub fn pixels_per_meter(&self) -> f32 {
+        self.resolution.y as f32 / self.height
+    }
+}
+
+fn apply_window_configs(
+    windows: Query<
+        (
+            Entity,
+            &SpatialEguiWindowConfig,
+            Option<&EguiRenderToTextureHandle>,
+            Option<&Handle<Mesh>>,
+            Has<Transform>,
+            Has<Visibility>,
+            Option<&MinimizedWindowState>,
+            Option<&WindowFrame>,
+            Option<&SpatialEguiAdaptiveResolution>,
+            Option<&SpatialEguiTextureSettings>,
+        ),
+        Changed<SpatialEguiWindowConfig>,
+    >,
+    frames: Query<(&Handle<Mesh>, &Handle<StandardMaterial>), With<Parent>>,
+    mut images: ResMut<Assets<Image>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut spawned: EventWriter<SpatialEguiWindowSpawned>,
+    mut cmds: Commands,
+) {
+    for (
+        entity,
+        config,
+        texture,
+        mesh,
+        has_transform,
+        has_visibility,
+        minimized,
+        frame,
+        adaptive,
+        texture_settings,
+    ) in &windows
+    {
+        let size = config.physical_size();
+        let mesh_builder = config.mesh_builder();
+        let texture_size = resolution::texture_size(config, adaptive, texture_settings);
+        let mut e = cmds.entity(entity);
+        // minimized windows get their field back when they are restored
+        if minimized.is_none() {
+            e.insert(Field::Cuboid(Cuboid::from_size(
+                config.surface_shape().bounds(size),
+            )));
+        }
+        e.insert((
             SpatialEguiWindowPhysicalSize(size),
-        );
-        world.insert_resource(materials);
-        world.insert_resource(meshes);
-        if let Some(target) = self.target_entity {
-            world.entity_mut(target).insert(bundle);
-        } else {
-            world.spawn(bundle);
+            // egui_size gets corrected for the pixels per point of the context in update_windows
+            SpatialEguiWindow {
+                size,
+                egui_size: egui::vec2(config.resolution.x as f32, config.resolution.y as f32),
+                shape: config.surface_shape(),
+            },
+        ));
+        match (texture.map(|texture| &texture.0), mesh) {
+            (Some(texture), Some(mesh)) => {
+                if let Some(image) = images.get_mut(texture) {
+                    if image.texture_descriptor.size != texture_size {
+                        image.resize(texture_size);
+                    }
+                }
+                meshes.insert(mesh, mesh_builder.content());
+            }
+            _ => {
+                let texture = images.add({
+                    // zeroed, so it's transparent until egui rendered to it
+                    let mut output_texture = Image {
+                        data: vec![0; (texture_size.width * texture_size.height * 4) as usize],
+                        ..default()
+                    };
+                    // mipmapping copies from it
+                    output_texture.texture_descriptor.usage |=
+                        TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC;
+                    output_texture.texture_descriptor.size = texture_size;
+                    output_texture
+                });
+                // the material is added by material::apply_window_materials
+                let mesh = meshes.add(mesh_builder.content());
+                e.insert((
+                    InputHandler::new(input_surface_capture_condition),
+                    EguiRenderToTextureHandle(texture),
+                    mesh,
+                    SpatialEguiWindowCursors::default(),
+                ));
+                if !has_transform {
+                    e.insert(TransformBundle::default());
+                }
+                if !has_visibility {
+                    e.insert(VisibilityBundle::default());
+                }
+                spawned.send(SpatialEguiWindowSpawned { window: entity });
+            }
+        }
+        let frame_material = StandardMaterial {
+            base_color: config.frame_color,
+            unlit: config.unlit,
+            ..default()
+        };
+        match frame.and_then(|frame| frames.get(frame.0).ok()) {
+            Some((frame_mesh, frame_material_handle)) => {
+                meshes.insert(frame_mesh, mesh_builder.frame());
+                materials.insert(frame_material_handle, frame_material);
+            }
+            None => {
+                let frame = cmds
+                    .spawn(PbrBundle {
+                        mesh: meshes.add(mesh_builder.frame()),
+                        material: materials.add(frame_material),
+                        ..default()
+                    })
+                    .set_parent(entity)
+                    .id();
+                cmds.entity(entity).insert(WindowFrame(frame));
+            }
         }
     }
 }
 
+/// The child entity showing the back and edges of a window, so they don't use the egui texture
+#[derive(Clone, Copy, Component)]
+pub(crate) struct WindowFrame(pub(crate) Entity);
+
 #[derive(Clone, Copy, Component, Debug)]
 pub struct SpatialEguiWindowPhysicalSize(pub Vec3);
 
@@ -335,53 +1174,101 @@ fn input_surface_capture_condition(
     method_query: Query<(
         Has<PointerInputMethod>,
         Option<&XrControllerInputMethodData>,
+        Option<&SpatialEguiControllerFaceButtons>,
         Option<&HandInputMethodData>,
         Option<&MouseInputMethodData>,
     )>,
+    owners: Query<(
+        Option<&SpatialEguiChromeElement>,
+        Option<&SpatialEguiWindowOrb>,
+    )>,
+    window_bindings: Query<&SpatialEguiInputBindings>,
+    global_bindings: Res<SpatialEguiInputBindings>,
+    visibility_query: Query<&InheritedVisibility>,
+    window_query: Query<&SpatialEguiWindow>,
+    mut transparent_query: Query<(
+        &SpatialEguiTransparentWindow,
+        &SpatialEguiWindow,
+        &mut EguiContext,
+    )>,
     mut giz: Gizmos,
 ) -> bool {
-    let Ok((is_pointer_method, xr_controller_data, xr_hand_data, mouse_data)) =
+    // hidden windows shouldn't block input to what's behind them
+    if visibility_query
+        .get(ctx.handler)
+        .is_ok_and(|visibility| !visibility.get())
+    {
+        return false;
+    }
+    let Ok((is_pointer_method, xr_controller_data, face_buttons, xr_hand_data, mouse_data)) =
         method_query.get(ctx.input_method)
     else {
         warn!("invald input method");
         return false;
     };
+    // input on the empty parts of transparent windows goes to what's behind them
+    if let Ok((transparent, window, mut egui_ctx)) = transparent_query.get_mut(ctx.handler) {
+        if transparency::passes_through(
+            transparent,
+            window,
+            egui_ctx.bypass_change_detection().get_mut(),
+            ctx.closest_point,
+            &ctx.input_method_location,
+            is_pointer_method,
+        ) {
+            return false;
+        }
+    }
     if is_pointer_method {
         return true;
     }
-    let distance = ctx
-        .closest_point
-        .distance(ctx.input_method_location.translation);
-    if distance > MAX_CLOSE_RANGE_INTERACTION_DISTANCE {
-        return false;
-    }
-    if distance <= f32::EPSILON {
+    let location = ctx.input_method_location.translation;
+    let window = window_query.get(ctx.handler).ok();
+    if window.map_or(
+        ctx.closest_point.distance(location) <= f32::EPSILON,
+        |window| window.is_touched(ctx.closest_point, location),
+    ) {
         return true;
     }
+    // the field of curved windows is a box around them, near methods measure to the surface
+    let surface_point = window.map_or(ctx.closest_point, |window| {
+        window.shape.hit(
+            ctx.closest_point,
+            &ctx.input_method_location,
+            false,
+            window.size.z,
+        )
+    });
+    if surface_point.distance(location) > MAX_CLOSE_RANGE_INTERACTION_DISTANCE {
+        return false;
+    }
 
     let mat = ctx.handler_location.compute_matrix();
     giz.line(
-        mat.transform_point3(ctx.closest_point),
-        mat.transform_point3(ctx.input_method_location.translation),
+        mat.transform_point3(surface_point),
+        mat.transform_point3(location),
         css::WHITE,
     );
 
-    let mut capture = false;
+    // the same bindings read_suis_input uses for the window
+    let bindings = input_bindings(ctx.handler, &owners, &window_bindings, &global_bindings);
+    let mut capture = bindings.any_pressed(xr_controller_data, face_buttons, mouse_data);
     if let Some(mouse) = mouse_data {
-        capture |= mouse.left_button.pressed;
-        capture |= mouse.right_button.pressed;
         capture |= mouse.discrete_scroll != Vec2::ZERO;
         capture |= mouse.continuous_scroll != Vec2::ZERO;
     }
     if let Some(hand) = xr_hand_data {
-        let hand = hand.get_in_relative_space(&ctx.handler_location);
-        capture |= hand.index.tip.pos.distance(hand.thumb.tip.pos)
-            < (0.002 + hand.index.tip.radius + hand.thumb.tip.radius);
+        capture |= [
+            bindings.hand_click,
+            bindings.hand_secondary,
+            bindings.hand_middle,
+        ]
+        .into_iter()
+        .flatten()
+        .any(|gesture| gesture.active(hand, &ctx.handler_location));
     }
     if let Some(controller) = xr_controller_data {
-        capture |= controller.trigger_pulled;
-        capture |= controller.squeezed;
-        capture |= controller.stick_pos.y.abs() > 0.1;
+        capture |= controller.stick_pos.y.abs() > bindings.stick_dead_zone;
     }
     capture
 }
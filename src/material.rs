@@ -0,0 +1,195 @@
+use std::marker::PhantomData;
+
+use bevy::{prelude::*, utils::HashSet};
+use bevy_egui::{EguiContext, EguiRenderToTextureHandle};
+
+use crate::{
+    minimize::MinimizedWindowState, texture::MipmappedEguiTexture,
+    transparency::SpatialEguiTransparentWindow, SpatialEguiWindowConfig,
+};
+
+/// A material that can show the egui texture of a window
+pub trait SpatialEguiMaterial: Material + Clone {
+    fn egui_texture(&self) -> Option<&Handle<Image>>;
+    fn set_egui_texture(&mut self, texture: Handle<Image>);
+    /// Applies the window config, like whether the window is lit
+    fn configure(&mut self, _config: &SpatialEguiWindowConfig) {}
+}
+
+impl SpatialEguiMaterial for StandardMaterial {
+    fn egui_texture(&self) -> Option<&Handle<Image>> {
+        self.base_color_texture.as_ref()
+    }
+
+    fn set_egui_texture(&mut self, texture: Handle<Image>) {
+        self.base_color_texture = Some(texture);
+    }
+
+    fn configure(&mut self, config: &SpatialEguiWindowConfig) {
+        self.unlit = config.unlit;
+    }
+}
+
+/// The material a window is drawn with, the window gets a copy of it showing its egui texture.
+/// Changing it replaces the material of the window.
+///
+/// Windows without this component use a [`StandardMaterial`], which blends with premultiplied
+/// alpha like egui renders when the egui panels or windows are translucent, or with the alpha mode of a
+/// [`SpatialEguiTransparentWindow`]. Other materials need a [`SpatialEguiMaterialPlugin`].
+#[derive(Clone, Component, Debug)]
+pub struct SpatialEguiWindowMaterial<M: SpatialEguiMaterial>(pub M);
+
+/// Lets windows use `M` through [`SpatialEguiWindowMaterial`], bevy's `MaterialPlugin` for `M`
+/// still has to be added. [`StandardMaterial`] is always supported.
+pub struct SpatialEguiMaterialPlugin<M>(PhantomData<M>);
+
+impl<M> Default for SpatialEguiMaterialPlugin<M> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<M: SpatialEguiMaterial> Plugin for SpatialEguiMaterialPlugin<M> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            apply_window_materials::<M>
+                .after(crate::texture::apply_texture_settings)
+                .before(add_default_window_materials),
+        );
+    }
+}
+
+/// The window has a material from a [`SpatialEguiWindowMaterial`]
+#[derive(Clone, Copy, Component)]
+pub(crate) struct WindowMaterial;
+
+/// The window uses the built in material
+#[derive(Clone, Copy, Component)]
+pub(crate) struct DefaultWindowMaterial;
+
+fn default_window_material(alpha_mode: AlphaMode) -> StandardMaterial {
+    StandardMaterial {
+        base_color: Color::WHITE,
+        alpha_mode,
+        ..default()
+    }
+}
+
+fn default_alpha_mode(
+    transparent: Option<&SpatialEguiTransparentWindow>,
+    egui_ctx: Option<Mut<EguiContext>>,
+) -> AlphaMode {
+    if let Some(transparent) = transparent {
+        return transparent.alpha_mode;
+    }
+    let Some(mut egui_ctx) = egui_ctx else {
+        return AlphaMode::Opaque;
+    };
+    // only reads the style, so the context isn't marked as changed every frame
+    let style = egui_ctx.bypass_change_detection().get_mut().style();
+    if style.visuals.panel_fill.a() < u8::MAX || style.visuals.window_fill.a() < u8::MAX {
+        // egui renders premultiplied colors, straight alpha blending darkens the edges
+        AlphaMode::Premultiplied
+    } else {
+        AlphaMode::Opaque
+    }
+}
+
+pub(crate) fn add_default_window_materials(
+    mut windows: Query<
+        (
+            Entity,
+            Option<&SpatialEguiTransparentWindow>,
+            Option<&mut EguiContext>,
+        ),
+        (
+            With<SpatialEguiWindowConfig>,
+            Without<WindowMaterial>,
+            Without<SpatialEguiWindowMaterial<StandardMaterial>>,
+        ),
+    >,
+    mut default_windows: Query<
+        (
+            Option<&SpatialEguiTransparentWindow>,
+            Option<&mut EguiContext>,
+            &mut SpatialEguiWindowMaterial<StandardMaterial>,
+        ),
+        // minimized windows have no egui context to read the style from
+        (With<DefaultWindowMaterial>, Without<MinimizedWindowState>),
+    >,
+    mut cmds: Commands,
+) {
+    for (window, transparent, egui_ctx) in &mut windows {
+        cmds.entity(window).insert((
+            SpatialEguiWindowMaterial(default_window_material(default_alpha_mode(
+                transparent,
+                egui_ctx,
+            ))),
+            DefaultWindowMaterial,
+        ));
+    }
+    for (transparent, egui_ctx, mut material) in &mut default_windows {
+        let alpha_mode = default_alpha_mode(transparent, egui_ctx);
+        if material.0.alpha_mode != alpha_mode {
+            material.0.alpha_mode = alpha_mode;
+        }
+    }
+}
+
+pub(crate) fn apply_window_materials<M: SpatialEguiMaterial>(
+    windows: Query<(
+        Entity,
+        Ref<SpatialEguiWindowConfig>,
+        Ref<SpatialEguiWindowMaterial<M>>,
+        Option<&Handle<M>>,
+        Option<&EguiRenderToTextureHandle>,
+        Option<&MipmappedEguiTexture>,
+    )>,
+    mut image_events: EventReader<AssetEvent<Image>>,
+    mut materials: ResMut<Assets<M>>,
+    mut cmds: Commands,
+) {
+    let modified_images = image_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect::<HashSet<_>>();
+    for (window, config, template, material, texture, mipmapped) in &windows {
+        let Some(texture) = texture.map(|texture| &texture.0) else {
+            continue;
+        };
+        // mipmapped windows show the mipmapped copy of their texture
+        let shown = mipmapped.map_or(texture, |mipmapped| &mipmapped.target);
+        let new_material = || {
+            let mut material = template.0.clone();
+            material.set_egui_texture(shown.clone());
+            material.configure(&config);
+            material
+        };
+        let Some(handle) = material else {
+            cmds.entity(window)
+                .insert((materials.add(new_material()), WindowMaterial));
+            continue;
+        };
+        if template.is_changed() {
+            materials.insert(handle, new_material());
+            continue;
+        }
+        // reallocated textures are only picked up when the material changes
+        let needs_update = config.is_changed()
+            || modified_images.contains(&shown.id())
+            || materials
+                .get(handle)
+                .is_some_and(|material| material.egui_texture() != Some(shown));
+        if !needs_update {
+            continue;
+        }
+        if let Some(material) = materials.get_mut(handle) {
+            material.set_egui_texture(shown.clone());
+            material.configure(&config);
+        }
+    }
+}
@@ -0,0 +1,256 @@
+use std::time::Duration;
+
+use bevy::{ecs::entity::EntityHashMap, prelude::*};
+use bevy_egui::{EguiContext, EguiRenderToTextureHandle};
+use bevy_suis::{Field, InputHandler};
+
+use crate::{
+    input_surface_capture_condition, parent_relative_transform, repaint::PausedSpatialEguiTexture,
+    SpatialEguiFocus, SpatialEguiWindow, WindowInput,
+};
+
+/// Shrinks the window into a small orb, clicking the orb or removing the component restores it.
+///
+/// While minimized the window doesn't take input and its egui texture isn't rendered to. Its
+/// [`EguiContext`] is taken off until it's restored, so egui doesn't run a pass for it and the
+/// systems drawing it skip it.
+#[derive(Clone, Copy, Component, Debug)]
+pub struct MinimizedSpatialEguiWindow;
+
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct SpatialEguiMinimizeSettings {
+    /// Radius of the orb in meters
+    pub orb_radius: f32,
+    pub animation_duration: Duration,
+}
+
+impl Default for SpatialEguiMinimizeSettings {
+    fn default() -> Self {
+        Self {
+            orb_radius: 0.05,
+            animation_duration: Duration::from_millis(250),
+        }
+    }
+}
+
+/// The orb a minimized window was shrunk into, can be grabbed and clicked to restore the window
+#[derive(Clone, Copy, Component, Debug)]
+pub struct SpatialEguiWindowOrb {
+    pub window: Entity,
+}
+
+/// What is needed to restore a minimized window
+#[derive(Clone, Component)]
+pub(crate) struct MinimizedWindowState {
+    orb: Entity,
+    restore_transform: Transform,
+    egui_ctx: Option<EguiContext>,
+}
+
+#[derive(Clone, Copy, Component)]
+pub(crate) struct MinimizeAnimation {
+    from: Transform,
+    to: Transform,
+    elapsed: Duration,
+    minimizing: bool,
+}
+
+pub(crate) fn minimize_windows(
+    windows: Query<
+        (
+            Entity,
+            &Transform,
+            &GlobalTransform,
+            Option<&EguiRenderToTextureHandle>,
+            Option<&EguiContext>,
+            Option<&Parent>,
+        ),
+        Added<MinimizedSpatialEguiWindow>,
+    >,
+    settings: Res<SpatialEguiMinimizeSettings>,
+    mut focus: ResMut<SpatialEguiFocus>,
+    gt_query: Query<&GlobalTransform>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut cmds: Commands,
+) {
+    for (window, transform, window_gt, texture, egui_ctx, parent) in &windows {
+        let Some(texture) = texture.map(|texture| &texture.0) else {
+            continue;
+        };
+        // keyboard input would pile up until the window is restored
+        if focus.window == Some(window) {
+            focus.clear();
+        }
+        let orb_transform = Transform::from_translation(window_gt.translation());
+        let orb = cmds
+            .spawn((
+                PbrBundle {
+                    mesh: meshes.add(Sphere::new(settings.orb_radius)),
+                    // the orb shows what's on the window
+                    material: materials.add(StandardMaterial {
+                        base_color_texture: Some(texture.clone()),
+                        unlit: true,
+                        ..default()
+                    }),
+                    transform: orb_transform,
+                    ..default()
+                },
+                Field::Cuboid(Cuboid::from_length(settings.orb_radius * 2.0)),
+                InputHandler::new(input_surface_capture_condition),
+                SpatialEguiWindowOrb { window },
+            ))
+            .id();
+        // shrink into the orb, in the parent space of the window
+        let to = parent_relative_transform(
+            &orb_transform.with_scale(Vec3::ZERO).into(),
+            parent.and_then(|e| gt_query.get(e.get()).ok()),
+        );
+        cmds.entity(window)
+            .insert((
+                MinimizedWindowState {
+                    orb,
+                    restore_transform: *transform,
+                    egui_ctx: egui_ctx.cloned(),
+                },
+                MinimizeAnimation {
+                    from: *transform,
+                    to,
+                    elapsed: Duration::ZERO,
+                    minimizing: true,
+                },
+                // the texture keeps what egui rendered last
+                PausedSpatialEguiTexture,
+            ))
+            .remove::<(Field, EguiContext)>();
+    }
+}
+
+pub(crate) fn restore_windows(
+    mut restored: RemovedComponents<MinimizedSpatialEguiWindow>,
+    windows: Query<(&MinimizedWindowState, &Transform, &SpatialEguiWindow)>,
+    mut cmds: Commands,
+) {
+    for window in restored.read() {
+        let Ok((state, transform, spatial_window)) = windows.get(window) else {
+            continue;
+        };
+        cmds.entity(state.orb).despawn_recursive();
+        let mut window_cmds = cmds.entity(window);
+        if let Some(egui_ctx) = state.egui_ctx.clone() {
+            window_cmds.insert(egui_ctx);
+        }
+        window_cmds
+            .insert((
+                Field::Cuboid(Cuboid::from_size(
+                    spatial_window.shape.bounds(spatial_window.size),
+                )),
+                Visibility::Inherited,
+                MinimizeAnimation {
+                    from: *transform,
+                    to: state.restore_transform,
+                    elapsed: Duration::ZERO,
+                    minimizing: false,
+                },
+            ))
+            .remove::<(MinimizedWindowState, PausedSpatialEguiTexture)>();
+    }
+}
+
+pub(crate) fn animate_minimize(
+    mut windows: Query<(Entity, &mut MinimizeAnimation, &mut Transform)>,
+    settings: Res<SpatialEguiMinimizeSettings>,
+    time: Res<Time>,
+    mut cmds: Commands,
+) {
+    for (window, mut animation, mut transform) in &mut windows {
+        animation.elapsed += time.delta();
+        let t = if settings.animation_duration.is_zero() {
+            1.0
+        } else {
+            (animation.elapsed.as_secs_f32() / settings.animation_duration.as_secs_f32()).min(1.0)
+        };
+        // ease out
+        let t = 1.0 - (1.0 - t).powi(2);
+        *transform = Transform {
+            translation: animation.from.translation.lerp(animation.to.translation, t),
+            rotation: animation.from.rotation.slerp(animation.to.rotation, t),
+            scale: animation.from.scale.lerp(animation.to.scale, t),
+        };
+        if t < 1.0 {
+            continue;
+        }
+        let mut window_cmds = cmds.entity(window);
+        window_cmds.remove::<MinimizeAnimation>();
+        if animation.minimizing {
+            window_cmds.insert(Visibility::Hidden);
+        }
+    }
+}
+
+/// Clicking an orb restores its window, grabbing it moves the orb
+pub(crate) fn route_orb_input(
+    In(inputs): In<Vec<WindowInput>>,
+    mut orbs: Query<(&SpatialEguiWindowOrb, &mut Transform, &GlobalTransform)>,
+    mut grabs: Local<EntityHashMap<(Entity, Transform)>>,
+    mut cmds: Commands,
+) -> Vec<WindowInput> {
+    let mut window_inputs = Vec::with_capacity(inputs.len());
+    for input in inputs {
+        let Ok((orb, mut orb_transform, orb_gt)) = orbs.get_mut(input.handler) else {
+            window_inputs.push(input);
+            continue;
+        };
+        if input.methods.iter().any(|method| method.state.click) {
+            if let Some(mut window_cmds) = cmds.get_entity(orb.window) {
+                window_cmds.remove::<MinimizedSpatialEguiWindow>();
+            }
+            continue;
+        }
+        let grab = grabs
+            .get(&input.handler)
+            .filter(|(method, _)| {
+                input
+                    .methods
+                    .iter()
+                    .any(|m| m.input_method == *method && m.state.grab)
+            })
+            .copied()
+            .or_else(|| {
+                let method = input.methods.iter().find(|method| method.state.grab)?;
+                Some((method.input_method, orb_gt.reparented_to(&method.method_gt)))
+            });
+        match grab {
+            Some((method, relative)) => {
+                let method_gt = input
+                    .methods
+                    .iter()
+                    .find(|m| m.input_method == method)
+                    .map(|m| m.method_gt)
+                    .unwrap_or_default();
+                *orb_transform = method_gt.mul_transform(relative).compute_transform();
+                grabs.insert(input.handler, (method, relative));
+            }
+            None => {
+                grabs.remove(&input.handler);
+            }
+        }
+    }
+    window_inputs
+}
+
+/// Orbs of windows that were despawned or restored
+pub(crate) fn despawn_stale_orbs(
+    orbs: Query<(Entity, &SpatialEguiWindowOrb)>,
+    windows: Query<&MinimizedWindowState>,
+    mut cmds: Commands,
+) {
+    for (entity, orb) in &orbs {
+        if windows
+            .get(orb.window)
+            .map_or(true, |state| state.orb != entity)
+        {
+            cmds.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+
+use crate::{
+    grab::GrabbedEguiWindow,
+    minimize::{MinimizeAnimation, MinimizedSpatialEguiWindow},
+    parent_relative_transform,
+    resize::ResizingEguiWindow,
+};
+
+/// Windows that aren't placed by something else right now
+type Placeable = (
+    Without<GrabbedEguiWindow>,
+    Without<ResizingEguiWindow>,
+    Without<MinimizedSpatialEguiWindow>,
+    Without<MinimizeAnimation>,
+);
+
+type Transforms<'w, 's> = Query<'w, 's, (&'static mut Transform, Option<&'static Parent>)>;
+
+/// World transform of `entity` from its and its ancestors' [`Transform`]s, unlike its
+/// [`GlobalTransform`] it already includes the changes of this frame
+fn world_transform(entity: Entity, transforms: &Transforms) -> Option<GlobalTransform> {
+    let (transform, parent) = transforms.get(entity).ok()?;
+    let parent_gt = parent
+        .and_then(|parent| world_transform(parent.get(), transforms))
+        .unwrap_or_default();
+    Some(parent_gt.mul_transform(*transform))
+}
+
+/// Moves `window` toward `target` in world space
+fn place_window(window: Entity, transforms: &mut Transforms, target: Transform, t: f32) {
+    let Some(window_gt) = world_transform(window, transforms) else {
+        return;
+    };
+    let parent_gt = transforms
+        .get(window)
+        .ok()
+        .and_then(|(_, parent)| parent)
+        .and_then(|parent| world_transform(parent.get(), transforms));
+    if let Ok((mut transform, _)) = transforms.get_mut(window) {
+        move_window(&mut transform, &window_gt, parent_gt.as_ref(), target, t);
+    }
+}
+
+/// Turns the window so its egui side faces `camera`
+#[derive(Clone, Copy, Component, Debug)]
+pub struct FaceCamera {
+    pub camera: Entity,
+    /// Only rotate around the Y axis so the window stays upright
+    pub yaw_only: bool,
+}
+
+impl FaceCamera {
+    pub fn new(camera: Entity) -> Self {
+        Self {
+            camera,
+            yaw_only: true,
+        }
+    }
+}
+
+/// Moves the window back in front of `head` once it's outside of the view cone
+#[derive(Clone, Copy, Component, Debug)]
+pub struct LazyFollow {
+    pub head: Entity,
+    /// Distance in front of the head in meters
+    pub distance: f32,
+    /// Half angle of the view cone in radians
+    pub max_angle: f32,
+    /// Time in seconds it takes to cover most of the way back
+    pub damping: f32,
+    following: bool,
+}
+
+impl LazyFollow {
+    pub fn new(head: Entity) -> Self {
+        Self {
+            head,
+            distance: 1.0,
+            max_angle: 35f32.to_radians(),
+            damping: 0.2,
+            following: false,
+        }
+    }
+}
+
+/// Keeps the window at `offset` relative to `head`
+#[derive(Clone, Copy, Component, Debug)]
+pub struct HeadLocked {
+    pub head: Entity,
+    /// Where the window sits relative to the head, the egui side faces the head by default
+    pub offset: Transform,
+    /// Time in seconds it takes to cover most of the way to the target, 0.0 is rigid
+    pub damping: f32,
+}
+
+impl HeadLocked {
+    pub fn new(head: Entity) -> Self {
+        Self {
+            head,
+            offset: Transform::from_xyz(0.0, 0.0, -1.0).with_rotation(Quat::from_rotation_y(PI)),
+            damping: 0.1,
+        }
+    }
+}
+
+/// How far to move toward the target this frame
+pub(crate) fn damping_factor(damping: f32, delta: f32) -> f32 {
+    if damping <= 0.0 {
+        1.0
+    } else {
+        1.0 - (-delta / damping).exp()
+    }
+}
+
+/// The egui side of a window is its -Z face, so looking at a point turns the ui toward it
+pub(crate) fn facing(window_pos: Vec3, target: Vec3, yaw_only: bool) -> Option<Quat> {
+    let target = match yaw_only {
+        true => target.with_y(window_pos.y),
+        false => target,
+    };
+    let dir = (target - window_pos).try_normalize()?;
+    Some(Transform::IDENTITY.looking_to(dir, Vec3::Y).rotation)
+}
+
+pub(crate) fn move_window(
+    transform: &mut Transform,
+    window_gt: &GlobalTransform,
+    parent: Option<&GlobalTransform>,
+    target: Transform,
+    t: f32,
+) {
+    let (scale, rotation, translation) = window_gt.to_scale_rotation_translation();
+    let world = Transform {
+        translation: translation.lerp(target.translation, t),
+        rotation: rotation.slerp(target.rotation, t),
+        scale,
+    };
+    *transform = parent_relative_transform(&world.into(), parent);
+}
+
+pub(crate) fn face_camera(
+    windows: Query<(Entity, &FaceCamera), Placeable>,
+    mut transforms: Transforms,
+) {
+    for (window, face_camera) in &windows {
+        let (Some(window_gt), Some(camera_gt)) = (
+            world_transform(window, &transforms),
+            world_transform(face_camera.camera, &transforms),
+        ) else {
+            continue;
+        };
+        let Some(rotation) = facing(
+            window_gt.translation(),
+            camera_gt.translation(),
+            face_camera.yaw_only,
+        ) else {
+            continue;
+        };
+        let target = Transform::from_translation(window_gt.translation()).with_rotation(rotation);
+        place_window(window, &mut transforms, target, 1.0);
+    }
+}
+
+pub(crate) fn lazy_follow(
+    mut windows: Query<(Entity, &mut LazyFollow), Placeable>,
+    mut transforms: Transforms,
+    time: Res<Time>,
+) {
+    for (window, mut follow) in &mut windows {
+        let (Some(window_gt), Some(head_gt)) = (
+            world_transform(window, &transforms),
+            world_transform(follow.head, &transforms),
+        ) else {
+            continue;
+        };
+        let head_pos = head_gt.translation();
+        let forward = head_gt
+            .forward()
+            .with_y(0.0)
+            .try_normalize()
+            .unwrap_or(Vec3::NEG_Z);
+        let target_pos = head_pos + (forward * follow.distance);
+        let angle = head_gt
+            .forward()
+            .angle_between(window_gt.translation() - head_pos);
+        if angle > follow.max_angle {
+            follow.following = true;
+        }
+        if !follow.following {
+            continue;
+        }
+        let Some(rotation) = facing(target_pos, head_pos, true) else {
+            continue;
+        };
+        let target = Transform::from_translation(target_pos).with_rotation(rotation);
+        let t = damping_factor(follow.damping, time.delta_seconds());
+        place_window(window, &mut transforms, target, t);
+        // stop once the window is back, it only moves again when it leaves the view cone
+        if window_gt.translation().distance(target_pos) < 0.01 {
+            follow.following = false;
+        }
+    }
+}
+
+pub(crate) fn head_locked(
+    windows: Query<(Entity, &HeadLocked), Placeable>,
+    mut transforms: Transforms,
+    time: Res<Time>,
+) {
+    for (window, locked) in &windows {
+        let Some(head_gt) = world_transform(locked.head, &transforms) else {
+            continue;
+        };
+        let target = head_gt.mul_transform(locked.offset).compute_transform();
+        let t = damping_factor(locked.damping, time.delta_seconds());
+        place_window(window, &mut transforms, target, t);
+    }
+}
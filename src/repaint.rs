@@ -0,0 +1,144 @@
+use std::time::Duration;
+
+use bevy::{
+    ecs::entity::EntityHashMap,
+    prelude::*,
+    render::{
+        extract_component::{ExtractComponent, ExtractComponentPlugin},
+        Render, RenderApp, RenderSet,
+    },
+};
+use bevy_egui::{egui, EguiContext, EguiInput, EguiRenderToTextureHandle};
+
+use crate::{
+    minimize::{MinimizedSpatialEguiWindow, MinimizedWindowState},
+    resolution::SpatialEguiAdaptiveResolution,
+    SpatialEguiWindowConfig,
+};
+
+/// When the egui texture of a window is rendered, windows without this component render every
+/// frame. Skipped frames keep showing the last rendered texture.
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq)]
+pub enum SpatialEguiRepaintPolicy {
+    #[default]
+    Always,
+    /// Only after the window got input, was reconfigured or egui requested a repaint
+    OnDemand,
+    /// Like [`SpatialEguiRepaintPolicy::OnDemand`], but at most this many times per second
+    MaxFps(f32),
+}
+
+/// Added to windows whose egui texture currently isn't rendered to. The texture handle stays on
+/// the window, bevy_egui would set up its render pass again if it got a new one.
+#[derive(Clone, Copy, Component, Debug, ExtractComponent)]
+pub struct PausedSpatialEguiTexture;
+
+pub(crate) fn build(app: &mut App) {
+    app.add_plugins(ExtractComponentPlugin::<PausedSpatialEguiTexture>::default());
+    if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+        render_app.add_systems(
+            Render,
+            skip_paused_egui_passes.in_set(RenderSet::PrepareAssets),
+        );
+    }
+}
+
+/// bevy_egui only renders the contexts that have a texture handle in the render world
+fn skip_paused_egui_passes(
+    paused: Query<
+        Entity,
+        (
+            With<PausedSpatialEguiTexture>,
+            With<EguiRenderToTextureHandle>,
+        ),
+    >,
+    mut cmds: Commands,
+) {
+    for window in &paused {
+        cmds.entity(window).remove::<EguiRenderToTextureHandle>();
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct RepaintState {
+    dirty: bool,
+    /// Input that egui reads next frame
+    pending_input: bool,
+    /// Whether there was more than the pointer being gone
+    pointer_active: bool,
+    since_render: Duration,
+}
+
+pub(crate) fn apply_repaint_policies(
+    mut windows: Query<
+        (
+            Entity,
+            &SpatialEguiRepaintPolicy,
+            &mut EguiContext,
+            &mut EguiInput,
+            Has<PausedSpatialEguiTexture>,
+            Ref<SpatialEguiWindowConfig>,
+            Option<Ref<SpatialEguiAdaptiveResolution>>,
+        ),
+        (
+            Without<MinimizedSpatialEguiWindow>,
+            Without<MinimizedWindowState>,
+        ),
+    >,
+    unmanaged: Query<
+        Entity,
+        (
+            With<PausedSpatialEguiTexture>,
+            Without<SpatialEguiRepaintPolicy>,
+            Without<MinimizedWindowState>,
+        ),
+    >,
+    mut states: Local<EntityHashMap<RepaintState>>,
+    time: Res<Time>,
+    mut cmds: Commands,
+) {
+    let mut next_states = EntityHashMap::default();
+    for (window, policy, mut egui_ctx, mut egui_input, paused, config, adaptive) in &mut windows {
+        let mut state = states.remove(&window).unwrap_or(RepaintState {
+            dirty: true,
+            ..default()
+        });
+        state.since_render += time.delta();
+        state.dirty |= state.pending_input
+            || egui_ctx.get_mut().has_requested_repaint()
+            || config.is_changed()
+            || adaptive.is_some_and(|adaptive| adaptive.is_changed());
+        // windows nothing points at get a `PointerGone` every frame, only the first one matters
+        let active = egui_input
+            .events
+            .iter()
+            .any(|event| *event != egui::Event::PointerGone);
+        state.pending_input = active || (state.pointer_active && !egui_input.events.is_empty());
+        if !active && !state.pointer_active && !egui_input.events.is_empty() {
+            // egui already knows the pointer is gone, it would request a repaint for every event
+            egui_input.events.clear();
+        }
+        state.pointer_active = active;
+        let render = match *policy {
+            SpatialEguiRepaintPolicy::Always => true,
+            SpatialEguiRepaintPolicy::OnDemand => state.dirty,
+            SpatialEguiRepaintPolicy::MaxFps(fps) => {
+                state.dirty && state.since_render.as_secs_f32() >= fps.recip()
+            }
+        };
+        if render {
+            state.dirty = false;
+            state.since_render = Duration::ZERO;
+            if paused {
+                cmds.entity(window).remove::<PausedSpatialEguiTexture>();
+            }
+        } else if !paused {
+            cmds.entity(window).insert(PausedSpatialEguiTexture);
+        }
+        next_states.insert(window, state);
+    }
+    *states = next_states;
+    for window in &unmanaged {
+        cmds.entity(window).remove::<PausedSpatialEguiTexture>();
+    }
+}
@@ -0,0 +1,138 @@
+use bevy::prelude::*;
+
+use crate::window_mesh::SpatialEguiWindowShape;
+
+/// Allows a spatial window to be resized by grabbing it close to one of its edges or corners.
+#[derive(Clone, Copy, Component, Debug)]
+pub struct SpatialEguiWindowResizable {
+    /// Distance from the border (in meters) in which a grab resizes instead of moving the window
+    pub handle_size: f32,
+    pub min_size: Vec2,
+    pub max_size: Vec2,
+}
+
+impl Default for SpatialEguiWindowResizable {
+    fn default() -> Self {
+        Self {
+            handle_size: 0.05,
+            min_size: Vec2::splat(0.1),
+            max_size: Vec2::splat(5.0),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Component)]
+pub(crate) struct ResizingEguiWindow {
+    /// The input method resizing the window, other methods can't take over
+    pub(crate) input_method: Entity,
+    /// -1, 0 or 1 per axis, the edges that follow the input method
+    edges: Vec2,
+    /// Texture resolution per meter of window, kept constant while resizing
+    pixels_per_meter: f32,
+    /// Shape of the window when resizing started, the point is tracked on its surface
+    shape: SpatialEguiWindowShape,
+    start_point: Vec2,
+    start_size: Vec3,
+    start_transform: Transform,
+    start_location: GlobalTransform,
+}
+
+impl ResizingEguiWindow {
+    /// Returns `None` if `point` isn't on one of the resize handles
+    pub(crate) fn try_start(
+        input_method: Entity,
+        resizable: &SpatialEguiWindowResizable,
+        pixels_per_meter: f32,
+        shape: SpatialEguiWindowShape,
+        size: Vec3,
+        point: Vec2,
+        transform: Transform,
+        location: GlobalTransform,
+    ) -> Option<Self> {
+        let half_size = size / 2.0;
+        let edges = Vec2::new(
+            edge(point.x, half_size.x, resizable.handle_size),
+            edge(point.y, half_size.y, resizable.handle_size),
+        );
+        if edges == Vec2::ZERO {
+            return None;
+        }
+        Some(Self::new(
+            input_method,
+            edges,
+            pixels_per_meter,
+            shape,
+            size,
+            point,
+            transform,
+            location,
+        ))
+    }
+
+    /// Starts resizing the given `edges`, -1, 0 or 1 per axis
+    pub(crate) fn new(
+        input_method: Entity,
+        edges: Vec2,
+        pixels_per_meter: f32,
+        shape: SpatialEguiWindowShape,
+        size: Vec3,
+        point: Vec2,
+        transform: Transform,
+        location: GlobalTransform,
+    ) -> Self {
+        Self {
+            input_method,
+            edges,
+            pixels_per_meter,
+            shape,
+            start_point: point,
+            start_size: size,
+            start_transform: transform,
+            start_location: location,
+        }
+    }
+
+    /// Returns the new physical size and transform of the window, `method_location` is in the
+    /// current window space, the edges opposite to the grabbed ones stay in place
+    pub(crate) fn update(
+        &self,
+        resizable: &SpatialEguiWindowResizable,
+        method_location: &Transform,
+        is_pointer: bool,
+        location: &GlobalTransform,
+    ) -> (Vec2, Transform) {
+        // the point is tracked on the window as it was when resizing started
+        let method_location = location
+            .mul_transform(*method_location)
+            .reparented_to(&self.start_location);
+        let point = self
+            .shape
+            .extended_hit(&method_location, is_pointer, self.start_size.z);
+        let start_size = self.start_size.xy();
+        let size = (start_size + (point - self.start_point) * self.edges)
+            .clamp(resizable.min_size, resizable.max_size);
+        let offset = (size - start_size) / 2.0 * self.edges;
+        let transform = self
+            .start_transform
+            .mul_transform(self.shape.offset(offset, self.start_size.z));
+        (size, transform)
+    }
+
+    /// Texture resolution for a window of the given physical size
+    pub(crate) fn resolution(&self, size: Vec2) -> UVec2 {
+        (size * self.pixels_per_meter)
+            .round()
+            .as_uvec2()
+            .max(UVec2::ONE)
+    }
+}
+
+fn edge(v: f32, half_size: f32, handle_size: f32) -> f32 {
+    if v >= half_size - handle_size {
+        1.0
+    } else if v <= -half_size + handle_size {
+        -1.0
+    } else {
+        0.0
+    }
+}
@@ -0,0 +1,225 @@
+use bevy::{prelude::*, render::render_resource::Extent3d};
+use bevy_egui::{
+    egui, EguiContext, EguiInput, EguiRenderToTextureHandle, EguiSettings, RenderTargetSize,
+};
+
+use crate::{
+    texture::SpatialEguiTextureSettings, SpatialEguiWindowConfig, SpatialEguiWindowPhysicalSize,
+};
+
+/// Scales the texture of a window with how large it appears on screen, so text stays crisp up
+/// close while distant windows use less memory. `resolution` of the window config still decides
+/// the layout, egui renders the same layout at more or fewer pixels per point.
+#[derive(Clone, Copy, Component, Debug)]
+pub struct SpatialEguiAdaptiveResolution {
+    /// Texture pixels per pixel the window covers on screen
+    pub pixels_per_screen_pixel: f32,
+    /// Smallest fraction of `resolution` the texture is scaled to
+    pub min_scale: f32,
+    /// Largest multiple of `resolution` the texture is scaled to
+    pub max_scale: f32,
+    /// Relative change of the wanted scale before the texture is reallocated
+    pub hysteresis: f32,
+    scale: f32,
+}
+
+impl Default for SpatialEguiAdaptiveResolution {
+    fn default() -> Self {
+        Self {
+            pixels_per_screen_pixel: 1.0,
+            min_scale: 0.25,
+            max_scale: 2.0,
+            hysteresis: 0.25,
+            scale: 1.0,
+        }
+    }
+}
+
+impl SpatialEguiAdaptiveResolution {
+    /// The scale the texture currently has
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// The scale for a window covering `screen_size` pixels, `None` if no camera sees it. Keeps
+    /// the current scale unless the wanted one is off by more than the hysteresis.
+    pub fn next_scale(&self, resolution: UVec2, screen_size: Option<Vec2>) -> f32 {
+        let wanted = match screen_size {
+            Some(screen_size) => {
+                (screen_size / resolution.as_vec2()).max_element() * self.pixels_per_screen_pixel
+            }
+            None => self.min_scale,
+        };
+        let wanted = wanted.clamp(self.min_scale, self.max_scale);
+        if ((wanted / self.scale) - 1.0).abs() <= self.hysteresis
+            && wanted != self.min_scale
+            && wanted != self.max_scale
+        {
+            return self.scale;
+        }
+        wanted
+    }
+}
+
+/// How many texture pixels a window has per egui pixel
+pub(crate) fn texture_scale(
+    adaptive: Option<&SpatialEguiAdaptiveResolution>,
+    texture_settings: Option<&SpatialEguiTextureSettings>,
+) -> f32 {
+    adaptive.map_or(1.0, SpatialEguiAdaptiveResolution::scale)
+        * texture_settings.map_or(1.0, |settings| settings.supersampling)
+}
+
+/// Size of the texture of a window with an adaptive resolution or supersampling
+pub(crate) fn texture_size(
+    config: &SpatialEguiWindowConfig,
+    adaptive: Option<&SpatialEguiAdaptiveResolution>,
+    texture_settings: Option<&SpatialEguiTextureSettings>,
+) -> Extent3d {
+    let scale = texture_scale(adaptive, texture_settings);
+    let size = (config.resolution.as_vec2() * scale)
+        .round()
+        .as_uvec2()
+        .max(UVec2::ONE);
+    Extent3d {
+        width: size.x,
+        height: size.y,
+        depth_or_array_layers: 1,
+    }
+}
+
+/// Largest size in pixels the window covers on any camera. Windows partly behind a camera are so
+/// close that they count as infinitely large.
+fn screen_size(
+    window_gt: &GlobalTransform,
+    size: Vec3,
+    cameras: &Query<(&Camera, &GlobalTransform)>,
+) -> Option<Vec2> {
+    let half_size = size.xy() / 2.0;
+    let corners = [
+        Vec2::new(-half_size.x, half_size.y),
+        half_size,
+        Vec2::new(half_size.x, -half_size.y),
+        -half_size,
+    ]
+    .map(|corner| window_gt.transform_point(corner.extend(-size.z / 2.0)));
+    cameras
+        .iter()
+        .filter(|(camera, _)| camera.is_active)
+        .filter_map(|(camera, camera_gt)| {
+            let projected = corners.map(|corner| camera.world_to_viewport(camera_gt, corner));
+            let [Some(top_left), Some(top_right), Some(bottom_right), Some(bottom_left)] =
+                projected
+            else {
+                // corners behind the camera don't project, the window is only out of view if
+                // none of them do
+                return projected
+                    .iter()
+                    .any(Option::is_some)
+                    .then_some(Vec2::INFINITY);
+            };
+            // the longer of the opposite edges, seen at an angle one of them is shorter
+            let projected_size = Vec2::new(
+                top_left
+                    .distance(top_right)
+                    .max(bottom_left.distance(bottom_right)),
+                top_left
+                    .distance(bottom_left)
+                    .max(top_right.distance(bottom_right)),
+            );
+            Some(projected_size * camera.target_scaling_factor().unwrap_or(1.0))
+        })
+        .reduce(Vec2::max)
+}
+
+pub(crate) fn update_adaptive_resolution(
+    mut windows: Query<(
+        &mut SpatialEguiAdaptiveResolution,
+        &SpatialEguiWindowConfig,
+        &SpatialEguiWindowPhysicalSize,
+        &GlobalTransform,
+        &InheritedVisibility,
+        Option<&EguiRenderToTextureHandle>,
+        Option<&SpatialEguiTextureSettings>,
+    )>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    for (mut adaptive, config, size, window_gt, visibility, texture, texture_settings) in
+        &mut windows
+    {
+        // minimized windows keep their scale until they are restored
+        let Some(texture) = texture.map(|texture| &texture.0) else {
+            continue;
+        };
+        let screen_size = visibility
+            .get()
+            .then(|| screen_size(window_gt, size.0, &cameras))
+            .flatten();
+        let scale = adaptive.next_scale(config.resolution, screen_size);
+        if scale == adaptive.scale {
+            continue;
+        }
+        adaptive.scale = scale;
+        let texture_size = texture_size(config, Some(&adaptive), texture_settings);
+        if let Some(image) = images.get_mut(texture) {
+            if image.texture_descriptor.size != texture_size {
+                image.resize(texture_size);
+            }
+        }
+    }
+}
+
+/// Windows egui renders at another scale than 1, the ones [`scale_render_targets`] handles
+type ScaledWindow = Or<(
+    With<SpatialEguiAdaptiveResolution>,
+    With<SpatialEguiTextureSettings>,
+)>;
+
+/// Runs before bevy_egui sets the pixels per point of render to texture contexts to 1. That
+/// call would zoom egui back out if it saw the scale of the last frame, and
+/// [`scale_render_targets`] would zoom it in again every frame.
+pub(crate) fn reset_pixels_per_point(mut windows: Query<&mut EguiContext, ScaledWindow>) {
+    for mut ctx in &mut windows {
+        ctx.get_mut()
+            .input_mut(|input| input.pixels_per_point = 1.0);
+    }
+}
+
+/// Renders egui at more pixels per point the larger the texture is, like on a high DPI screen.
+/// Runs after bevy_egui sized the contexts, which assumes a scale of 1.
+pub(crate) fn scale_render_targets(
+    mut windows: Query<
+        (
+            &mut EguiContext,
+            &mut EguiInput,
+            &EguiRenderToTextureHandle,
+            Option<&mut RenderTargetSize>,
+            Option<&SpatialEguiAdaptiveResolution>,
+            Option<&SpatialEguiTextureSettings>,
+        ),
+        ScaledWindow,
+    >,
+    images: Res<Assets<Image>>,
+    egui_settings: Option<Res<EguiSettings>>,
+) {
+    for (mut ctx, mut egui_input, texture, render_target_size, adaptive, texture_settings) in
+        &mut windows
+    {
+        let Some(image) = images.get(&texture.0) else {
+            continue;
+        };
+        let scale = texture_scale(adaptive, texture_settings);
+        if let Some(mut render_target_size) = render_target_size {
+            render_target_size.scale_factor = scale;
+        }
+        let pixels_per_point =
+            scale * egui_settings.map_or(1.0, |egui_settings| egui_settings.scale_factor);
+        let size = image.size_f32() / pixels_per_point;
+        egui_input.screen_rect = Some(egui::Rect::from_min_size(
+            egui::Pos2::ZERO,
+            egui::vec2(size.x, size.y),
+        ));
+        ctx.get_mut().set_pixels_per_point(pixels_per_point);
+    }
+}
@@ -0,0 +1,129 @@
+use bevy::prelude::*;
+use bevy_egui::EguiContext;
+
+use crate::{
+    chrome::SpatialEguiChromeElement,
+    transparency::{self, SpatialEguiTransparentWindow},
+    InputState, MethodInput, MethodTarget, SpatialEguiWindow, SpatialEguiWindowPhysicalSize,
+    WindowInput,
+};
+
+/// A pointer that isn't backed by a bevy_suis input method, useful for tests and scripted input.
+///
+/// It interacts with `window` at the point of the window surface closest to its [`GlobalTransform`]
+/// and goes through the same egui event conversion as real input methods. Like them it goes
+/// through the empty parts of a [`SpatialEguiTransparentWindow`].
+///
+/// `window` can also be a [`SpatialEguiChromeElement`], to use the window chrome.
+#[derive(Clone, Copy, Component, Debug)]
+pub struct SimulatedSpatialPointer {
+    pub window: Entity,
+    pub click: bool,
+    pub secondary: bool,
+    pub middle: bool,
+    pub grab: bool,
+    /// How many Lines to scroll
+    pub discrete_scroll: Vec2,
+    /// How many Pixels to scroll
+    pub continuous_scroll: Vec2,
+}
+
+impl SimulatedSpatialPointer {
+    pub fn new(window: Entity) -> Self {
+        Self {
+            window,
+            click: false,
+            secondary: false,
+            middle: false,
+            grab: false,
+            discrete_scroll: Vec2::ZERO,
+            continuous_scroll: Vec2::ZERO,
+        }
+    }
+
+    fn input_state(&self) -> InputState {
+        InputState {
+            click: self.click,
+            grab: self.grab,
+            secondary: self.secondary,
+            middle: self.middle,
+            discrete_scroll: self.discrete_scroll,
+            continuous_scroll: self.continuous_scroll,
+            ..default()
+        }
+    }
+}
+
+pub(crate) fn add_simulated_input(
+    In(mut inputs): In<Vec<WindowInput>>,
+    pointers: Query<(Entity, &SimulatedSpatialPointer, &GlobalTransform)>,
+    windows: Query<(
+        &GlobalTransform,
+        Option<&SpatialEguiWindowPhysicalSize>,
+        Option<&SpatialEguiChromeElement>,
+    )>,
+    mut transparent_windows: Query<(
+        &SpatialEguiTransparentWindow,
+        &SpatialEguiWindow,
+        &mut EguiContext,
+    )>,
+) -> Vec<WindowInput> {
+    for (entity, pointer, pointer_gt) in &pointers {
+        let Ok((window_gt, window_size, element)) = windows.get(pointer.window) else {
+            continue;
+        };
+        let Some(size) = window_size
+            .map(|size| size.0)
+            .or(element.map(|element| element.size))
+        else {
+            continue;
+        };
+        let window_matrix = window_gt.compute_matrix().inverse();
+        let local_pos = window_matrix.transform_point3(pointer_gt.translation());
+        let half_size = size / 2.0;
+        if local_pos.x.abs() > half_size.x || local_pos.y.abs() > half_size.y {
+            continue;
+        }
+        let closest_point = local_pos.xy().extend(-half_size.z);
+        let input_method_location =
+            Transform::from_matrix(window_matrix * pointer_gt.compute_matrix());
+        if let Ok((transparent, window, mut egui_ctx)) = transparent_windows.get_mut(pointer.window)
+        {
+            if transparency::passes_through(
+                transparent,
+                window,
+                egui_ctx.bypass_change_detection().get_mut(),
+                closest_point,
+                &input_method_location,
+                true,
+            ) {
+                continue;
+            }
+        }
+        let method = MethodInput {
+            input_method: entity,
+            closest_point,
+            input_method_location,
+            method_gt: *pointer_gt,
+            is_pointer: true,
+            state: pointer.input_state(),
+            target: MethodTarget::Content,
+        };
+        match inputs
+            .iter_mut()
+            .find(|input| input.handler == pointer.window)
+        {
+            Some(input) => {
+                input.captured = true;
+                input.methods.push(method);
+            }
+            None => inputs.push(WindowInput {
+                handler: pointer.window,
+                handler_location: *window_gt,
+                captured: true,
+                methods: vec![method],
+            }),
+        }
+    }
+    inputs
+}
@@ -0,0 +1,231 @@
+use bevy::{color::palettes::css, prelude::*};
+
+use crate::{grab::GrabbedEguiWindow, parent_relative_transform, SpatialEguiWindowPhysicalSize};
+
+/// Snaps the window into place when it's released after being grabbed
+#[derive(Clone, Copy, Component, Debug)]
+pub struct SpatialEguiSnapSettings {
+    /// Size of the world grid the window position snaps to
+    pub grid: Option<f32>,
+    /// Increment in radians the window rotation snaps to
+    pub angle: Option<f32>,
+    /// Distance in meters in which the window snaps to the edges of other windows
+    pub window_edges: Option<f32>,
+    /// Distance in meters in which the window snaps onto a [`SpatialEguiSnapSurface`]
+    pub surfaces: Option<f32>,
+    /// Color of the outline previewing where the window will snap to
+    pub ghost_color: Color,
+}
+
+impl Default for SpatialEguiSnapSettings {
+    fn default() -> Self {
+        Self {
+            grid: None,
+            angle: None,
+            window_edges: Some(0.05),
+            surfaces: Some(0.1),
+            ghost_color: css::LIGHT_SKY_BLUE.into(),
+        }
+    }
+}
+
+/// A rectangle in the XY plane of the entity that windows can be placed on, like a desk or wall.
+/// Windows snap onto its -Z side, with their egui side facing the same way.
+#[derive(Clone, Copy, Component, Debug)]
+pub struct SpatialEguiSnapSurface {
+    pub size: Vec2,
+}
+
+/// Where the grabbed window will be placed when it's released, in world space
+#[derive(Clone, Copy, Component)]
+pub(crate) struct SnapPreview(Transform);
+
+impl SpatialEguiSnapSettings {
+    fn snap(
+        &self,
+        target: Transform,
+        size: Vec3,
+        windows: impl Iterator<Item = (GlobalTransform, Vec3)>,
+        surfaces: impl Iterator<Item = (GlobalTransform, Vec2)>,
+    ) -> Transform {
+        if let Some(distance) = self.surfaces {
+            if let Some(snapped) = surfaces
+                .filter_map(|(gt, surface)| snap_to_surface(target, size, gt, surface, distance))
+                .min_by(|a, b| {
+                    a.translation
+                        .distance(target.translation)
+                        .total_cmp(&b.translation.distance(target.translation))
+                })
+            {
+                return snapped;
+            }
+        }
+        if let Some(distance) = self.window_edges {
+            if let Some(snapped) = windows
+                .filter_map(|(gt, other)| {
+                    snap_to_window(target, size.xy(), gt, other.xy(), distance)
+                })
+                .min_by(|a, b| {
+                    a.translation
+                        .distance(target.translation)
+                        .total_cmp(&b.translation.distance(target.translation))
+                })
+            {
+                return snapped;
+            }
+        }
+        let mut snapped = target;
+        if let Some(angle) = self.angle.filter(|angle| *angle > 0.0) {
+            let (y, x, z) = target.rotation.to_euler(EulerRot::YXZ);
+            let round = |a: f32| (a / angle).round() * angle;
+            snapped.rotation = Quat::from_euler(EulerRot::YXZ, round(y), round(x), round(z));
+        }
+        if let Some(grid) = self.grid.filter(|grid| *grid > 0.0) {
+            snapped.translation = (target.translation / grid).round() * grid;
+        }
+        snapped
+    }
+}
+
+fn snap_to_surface(
+    target: Transform,
+    size: Vec3,
+    surface_gt: GlobalTransform,
+    surface_size: Vec2,
+    distance: f32,
+) -> Option<Transform> {
+    let surface = surface_gt.compute_transform();
+    let local = surface.rotation.inverse() * (target.translation - surface.translation);
+    let on_surface = local.xy().abs().cmple(surface_size / 2.0).all();
+    if !on_surface || local.z.abs() > distance + (size.z / 2.0) {
+        return None;
+    }
+    let local = local.xy().extend(-size.z / 2.0);
+    Some(Transform {
+        translation: surface.translation + (surface.rotation * local),
+        rotation: surface.rotation,
+        scale: target.scale,
+    })
+}
+
+/// Places the window next to another one with touching edges, aligning the outer edges or centers
+fn snap_to_window(
+    target: Transform,
+    size: Vec2,
+    other_gt: GlobalTransform,
+    other_size: Vec2,
+    distance: f32,
+) -> Option<Transform> {
+    let other = other_gt.compute_transform();
+    let local = other.rotation.inverse() * (target.translation - other.translation);
+    if local.z.abs() > distance {
+        return None;
+    }
+    let touching = (size + other_size) / 2.0;
+    let aligned = (other_size - size) / 2.0;
+    let snap_axis = |pos: f32, candidates: [f32; 3]| {
+        candidates
+            .into_iter()
+            .filter(|c| (pos - c).abs() < distance)
+            .min_by(|a, b| (pos - a).abs().total_cmp(&(pos - b).abs()))
+            .unwrap_or(pos)
+    };
+    let snapped = if (local.x.abs() - touching.x).abs() < distance && local.y.abs() < touching.y {
+        Vec2::new(
+            touching.x.copysign(local.x),
+            snap_axis(local.y, [-aligned.y, 0.0, aligned.y]),
+        )
+    } else if (local.y.abs() - touching.y).abs() < distance && local.x.abs() < touching.x {
+        Vec2::new(
+            snap_axis(local.x, [-aligned.x, 0.0, aligned.x]),
+            touching.y.copysign(local.y),
+        )
+    } else {
+        return None;
+    };
+    Some(Transform {
+        translation: other.translation + (other.rotation * snapped.extend(0.0)),
+        rotation: other.rotation,
+        scale: target.scale,
+    })
+}
+
+pub(crate) fn update_snap_previews(
+    windows: Query<
+        (
+            Entity,
+            &SpatialEguiSnapSettings,
+            &Transform,
+            &SpatialEguiWindowPhysicalSize,
+            Option<&Parent>,
+        ),
+        With<GrabbedEguiWindow>,
+    >,
+    other_windows: Query<(
+        Entity,
+        &GlobalTransform,
+        &SpatialEguiWindowPhysicalSize,
+        &InheritedVisibility,
+    )>,
+    surfaces: Query<(&GlobalTransform, &SpatialEguiSnapSurface)>,
+    gt_query: Query<&GlobalTransform>,
+    mut cmds: Commands,
+) {
+    for (entity, settings, transform, size, parent) in &windows {
+        let parent_gt = parent
+            .and_then(|e| gt_query.get(e.get()).ok())
+            .copied()
+            .unwrap_or_default();
+        let target = parent_gt.mul_transform(*transform).compute_transform();
+        let snapped = settings.snap(
+            target,
+            size.0,
+            other_windows
+                .iter()
+                .filter(|(e, .., visibility)| *e != entity && visibility.get())
+                .map(|(_, gt, size, _)| (*gt, size.0)),
+            surfaces.iter().map(|(gt, surface)| (*gt, surface.size)),
+        );
+        if snapped == target {
+            cmds.entity(entity).remove::<SnapPreview>();
+        } else {
+            cmds.entity(entity).insert(SnapPreview(snapped));
+        }
+    }
+}
+
+pub(crate) fn apply_snapping(
+    mut released: RemovedComponents<GrabbedEguiWindow>,
+    mut windows: Query<(&SnapPreview, &mut Transform, Option<&Parent>)>,
+    gt_query: Query<&GlobalTransform>,
+    mut cmds: Commands,
+) {
+    for entity in released.read() {
+        let Ok((preview, mut transform, parent)) = windows.get_mut(entity) else {
+            continue;
+        };
+        *transform = parent_relative_transform(
+            &preview.0.into(),
+            parent.and_then(|e| gt_query.get(e.get()).ok()),
+        );
+        cmds.entity(entity).remove::<SnapPreview>();
+    }
+}
+
+pub(crate) fn draw_snap_previews(
+    windows: Query<(
+        &SnapPreview,
+        &SpatialEguiSnapSettings,
+        &SpatialEguiWindowPhysicalSize,
+    )>,
+    mut gizmos: Gizmos,
+) {
+    for (preview, settings, size) in &windows {
+        gizmos.rect(
+            preview.0.translation,
+            preview.0.rotation,
+            size.0.xy(),
+            settings.ghost_color,
+        );
+    }
+}
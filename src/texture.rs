@@ -0,0 +1,457 @@
+use bevy::{
+    asset::embedded_asset,
+    core_pipeline::fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+    ecs::entity::EntityHashSet,
+    prelude::*,
+    render::{
+        extract_component::{ExtractComponent, ExtractComponentPlugin},
+        graph::CameraDriverLabel,
+        render_asset::RenderAssets,
+        render_graph::{
+            Node, NodeRunError, RenderGraph, RenderGraphContext, RenderGraphError, RenderLabel,
+        },
+        render_resource::{
+            binding_types::{sampler, texture_2d},
+            BindGroup, BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries,
+            CachedRenderPipelineId, ColorTargetState, ColorWrites, Extent3d, FilterMode,
+            FragmentState, LoadOp, Operations, PipelineCache, RenderPassColorAttachment,
+            RenderPassDescriptor, RenderPipelineDescriptor, Sampler, SamplerBindingType,
+            SamplerDescriptor, ShaderStages, SpecializedRenderPipeline, SpecializedRenderPipelines,
+            StoreOp, TextureDimension, TextureFormat, TextureId, TextureSampleType, TextureUsages,
+            TextureView, TextureViewDescriptor,
+        },
+        renderer::{RenderContext, RenderDevice},
+        texture::{
+            GpuImage, ImageFilterMode, ImageSampler, ImageSamplerDescriptor, TextureFormatPixelInfo,
+        },
+        Render, RenderApp, RenderSet,
+    },
+    utils::HashMap,
+};
+use bevy_egui::{render_systems::EguiPass, EguiRenderToTextureHandle};
+
+use crate::{
+    resolution::{texture_size, SpatialEguiAdaptiveResolution},
+    SpatialEguiWindowConfig,
+};
+
+/// How the egui texture of a window is sampled, to keep text from shimmering on distant or
+/// angled windows
+#[derive(Clone, Copy, Component, Debug)]
+pub struct SpatialEguiTextureSettings {
+    /// Generate mipmaps after egui rendered, the window then shows a mipmapped copy of its texture
+    pub mipmaps: bool,
+    /// Maximum anisotropy of the sampler, 1 turns anisotropic filtering off
+    pub anisotropy: u16,
+    /// Renders egui at this multiple of the resolution, sampling the texture scales it back down
+    pub supersampling: f32,
+}
+
+impl Default for SpatialEguiTextureSettings {
+    fn default() -> Self {
+        Self {
+            mipmaps: true,
+            anisotropy: 16,
+            supersampling: 1.0,
+        }
+    }
+}
+
+impl SpatialEguiTextureSettings {
+    fn sampler(&self) -> ImageSampler {
+        ImageSampler::Descriptor(ImageSamplerDescriptor {
+            mag_filter: ImageFilterMode::Linear,
+            min_filter: ImageFilterMode::Linear,
+            mipmap_filter: ImageFilterMode::Linear,
+            anisotropy_clamp: self.anisotropy.max(1),
+            ..ImageSamplerDescriptor::linear()
+        })
+    }
+}
+
+/// The egui texture of a window and the mipmapped copy the window shows, the copy is only
+/// mipmapped again on frames egui renders to the window
+#[derive(Clone, Component, ExtractComponent)]
+#[extract_component_filter(With<EguiRenderToTextureHandle>)]
+pub(crate) struct MipmappedEguiTexture {
+    source: Handle<Image>,
+    pub(crate) target: Handle<Image>,
+}
+
+fn mipmapped_image(size: Extent3d, format: TextureFormat, sampler: ImageSampler) -> Image {
+    let mip_level_count = size.max_mips(TextureDimension::D2);
+    let data_len = (0..mip_level_count)
+        .map(|level| size.mip_level_size(level, TextureDimension::D2))
+        .map(|size| size.width * size.height * format.pixel_size() as u32)
+        .sum::<u32>();
+    let mut image = Image {
+        data: vec![0; data_len as usize],
+        sampler,
+        ..default()
+    };
+    image.texture_descriptor.size = size;
+    image.texture_descriptor.format = format;
+    image.texture_descriptor.mip_level_count = mip_level_count;
+    image.texture_descriptor.usage |= TextureUsages::RENDER_ATTACHMENT;
+    image
+}
+
+pub(crate) fn apply_texture_settings(
+    windows: Query<(
+        Entity,
+        Ref<SpatialEguiTextureSettings>,
+        &SpatialEguiWindowConfig,
+        Option<&SpatialEguiAdaptiveResolution>,
+        Option<&EguiRenderToTextureHandle>,
+        Option<&MipmappedEguiTexture>,
+    )>,
+    removed: Query<
+        Entity,
+        (
+            With<MipmappedEguiTexture>,
+            Without<SpatialEguiTextureSettings>,
+        ),
+    >,
+    mut images: ResMut<Assets<Image>>,
+    mut cmds: Commands,
+) {
+    for (window, settings, config, adaptive, texture, mipmapped) in &windows {
+        let Some(source) = texture.map(|texture| &texture.0) else {
+            continue;
+        };
+        let size = texture_size(config, adaptive, Some(&settings));
+        if settings.is_changed() {
+            if let Some(image) = images.get_mut(source) {
+                // supersampling changes the size of the texture
+                if image.texture_descriptor.size != size {
+                    image.resize(size);
+                }
+                image.sampler = settings.sampler();
+            }
+        }
+        let Some((source_size, format)) = images.get(source).map(|image| {
+            let descriptor = &image.texture_descriptor;
+            (descriptor.size, descriptor.format)
+        }) else {
+            continue;
+        };
+        match (settings.mipmaps, mipmapped) {
+            (true, Some(mipmapped)) => {
+                let target = images.get(&mipmapped.target).map(|image| {
+                    let descriptor = &image.texture_descriptor;
+                    (descriptor.size, descriptor.format)
+                });
+                if settings.is_changed() || target != Some((source_size, format)) {
+                    images.insert(
+                        &mipmapped.target,
+                        mipmapped_image(source_size, format, settings.sampler()),
+                    );
+                }
+            }
+            (true, None) => {
+                let target = images.add(mipmapped_image(source_size, format, settings.sampler()));
+                cmds.entity(window).insert(MipmappedEguiTexture {
+                    source: source.clone(),
+                    target,
+                });
+            }
+            (false, Some(_)) => {
+                cmds.entity(window).remove::<MipmappedEguiTexture>();
+            }
+            (false, None) => {}
+        }
+    }
+    // the window material goes back to the egui texture
+    for window in &removed {
+        cmds.entity(window).remove::<MipmappedEguiTexture>();
+    }
+}
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+struct MipmapLabel;
+
+pub(crate) fn build(app: &mut App) {
+    embedded_asset!(app, "mipmap.wgsl");
+    app.add_plugins(ExtractComponentPlugin::<MipmappedEguiTexture>::default());
+    let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+        return;
+    };
+    let node = MipmapNode::from_world(render_app.world_mut());
+    let mut graph = render_app.world_mut().resource_mut::<RenderGraph>();
+    graph.add_node(MipmapLabel, node);
+    // cameras showing the windows should sample the new mipmaps, the egui passes are ordered
+    // before the node once bevy_egui added them
+    graph.add_node_edge(MipmapLabel, CameraDriverLabel);
+}
+
+pub(crate) fn finish(app: &mut App) {
+    if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+        render_app
+            .init_resource::<MipmapPipeline>()
+            .init_resource::<SpecializedRenderPipelines<MipmapPipeline>>()
+            .init_resource::<MipChains>()
+            .add_systems(
+                Render,
+                (
+                    order_mipmaps_after_egui_passes.in_set(RenderSet::Prepare),
+                    prepare_mip_chains.in_set(RenderSet::PrepareBindGroups),
+                ),
+            );
+    }
+}
+
+#[derive(Resource)]
+struct MipmapPipeline {
+    layout: BindGroupLayout,
+    sampler: Sampler,
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for MipmapPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let layout = render_device.create_bind_group_layout(
+            "spatial_egui_mipmap_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                ),
+            ),
+        );
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..default()
+        });
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://bevy_spatial_egui/mipmap.wgsl");
+        Self {
+            layout,
+            sampler,
+            shader,
+        }
+    }
+}
+
+impl SpecializedRenderPipeline for MipmapPipeline {
+    /// Format of the mipmapped copy
+    type Key = TextureFormat;
+
+    fn specialize(&self, format: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("spatial_egui_mipmap_pipeline".into()),
+            layout: vec![self.layout.clone()],
+            push_constant_ranges: Vec::new(),
+            vertex: fullscreen_shader_vertex_state(),
+            primitive: default(),
+            depth_stencil: None,
+            multisample: default(),
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs: Vec::new(),
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+        }
+    }
+}
+
+/// Adds an edge from the egui pass of each mipmapped window to the mipmap node, bevy_egui adds
+/// the pass when the window gets its texture
+fn order_mipmaps_after_egui_passes(
+    textures: Query<Entity, With<MipmappedEguiTexture>>,
+    mut graph: ResMut<RenderGraph>,
+    mut ordered: Local<EntityHashSet>,
+) {
+    ordered.retain(|window| textures.contains(*window));
+    for window in &textures {
+        if ordered.contains(&window) {
+            continue;
+        }
+        let pass = EguiPass {
+            window_index: window.index(),
+            window_generation: window.generation(),
+        };
+        // the pass may not be there yet, that's retried next frame
+        match graph.try_add_node_edge(pass, MipmapLabel) {
+            Ok(()) | Err(RenderGraphError::EdgeAlreadyExists(_)) => {
+                ordered.insert(window);
+            }
+            Err(_) => {}
+        }
+    }
+}
+
+/// The views of each mip of a mipmapped copy and the bind groups sampling them
+struct MipChain {
+    texture: TextureId,
+    pipeline: CachedRenderPipelineId,
+    views: Vec<TextureView>,
+    bind_groups: Vec<BindGroup>,
+    /// Whether the pipeline was ready when the chain was last prepared, the node mipmaps with it
+    /// that frame
+    ready: bool,
+    /// Whether the copy was filled since it was allocated, a new copy is filled once even if its
+    /// window is paused
+    filled: bool,
+}
+
+/// Mip chains by mipmapped copy, rebuilt when the copy is reallocated
+#[derive(Resource, Default)]
+struct MipChains(HashMap<AssetId<Image>, MipChain>);
+
+fn prepare_mip_chains(
+    textures: Query<&MipmappedEguiTexture>,
+    images: Res<RenderAssets<GpuImage>>,
+    mipmap_pipeline: Res<MipmapPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<MipmapPipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    render_device: Res<RenderDevice>,
+    mut chains: ResMut<MipChains>,
+) {
+    chains.0.retain(|target, _| images.get(*target).is_some());
+    for chain in chains.0.values_mut() {
+        chain.filled |= chain.ready;
+        chain.ready = pipeline_cache.get_render_pipeline(chain.pipeline).is_some();
+    }
+    for texture in &textures {
+        let Some(target) = images.get(&texture.target) else {
+            continue;
+        };
+        let id = texture.target.id();
+        if chains
+            .0
+            .get(&id)
+            .is_some_and(|chain| chain.texture == target.texture.id())
+        {
+            continue;
+        }
+        let views = (0..target.mip_level_count)
+            .map(|level| {
+                target.texture.create_view(&TextureViewDescriptor {
+                    base_mip_level: level,
+                    mip_level_count: Some(1),
+                    ..default()
+                })
+            })
+            .collect::<Vec<_>>();
+        // each mip is sampled to render the next one, the last one isn't sampled
+        let bind_groups = views[..views.len().saturating_sub(1)]
+            .iter()
+            .map(|view| {
+                render_device.create_bind_group(
+                    "spatial_egui_mipmap_bind_group",
+                    &mipmap_pipeline.layout,
+                    &BindGroupEntries::sequential((view, &mipmap_pipeline.sampler)),
+                )
+            })
+            .collect();
+        let pipeline =
+            pipelines.specialize(&pipeline_cache, &mipmap_pipeline, target.texture_format);
+        chains.0.insert(
+            id,
+            MipChain {
+                texture: target.texture.id(),
+                pipeline,
+                views,
+                bind_groups,
+                ready: pipeline_cache.get_render_pipeline(pipeline).is_some(),
+                filled: false,
+            },
+        );
+    }
+}
+
+/// Copies the egui textures into their mipmapped copies and downsamples them mip by mip
+struct MipmapNode {
+    textures: QueryState<(
+        &'static MipmappedEguiTexture,
+        Has<EguiRenderToTextureHandle>,
+    )>,
+}
+
+impl FromWorld for MipmapNode {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            textures: world.query(),
+        }
+    }
+}
+
+impl Node for MipmapNode {
+    fn update(&mut self, world: &mut World) {
+        self.textures.update_archetypes(world);
+    }
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let Some(chains) = world.get_resource::<MipChains>() else {
+            return Ok(());
+        };
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let images = world.resource::<RenderAssets<GpuImage>>();
+        // paused windows have their texture handle taken off in the render world
+        for (texture, rendered) in self.textures.iter_manual(world) {
+            let (Some(source), Some(target), Some(chain)) = (
+                images.get(&texture.source),
+                images.get(&texture.target),
+                chains.0.get(&texture.target.id()),
+            ) else {
+                continue;
+            };
+            if !rendered && chain.filled {
+                continue;
+            }
+            // the copy is reallocated in the main world when the size or format changes
+            if source.size != target.size
+                || source.texture_format != target.texture_format
+                || chain.texture != target.texture.id()
+            {
+                continue;
+            }
+            let Some(pipeline) = pipeline_cache.get_render_pipeline(chain.pipeline) else {
+                continue;
+            };
+            render_context.command_encoder().copy_texture_to_texture(
+                source.texture.as_image_copy(),
+                target.texture.as_image_copy(),
+                Extent3d {
+                    width: source.size.x,
+                    height: source.size.y,
+                    depth_or_array_layers: 1,
+                },
+            );
+            for (target_view, bind_group) in chain.views[1..].iter().zip(&chain.bind_groups) {
+                let mut pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+                    label: Some("spatial_egui_mipmap_pass"),
+                    color_attachments: &[Some(RenderPassColorAttachment {
+                        view: target_view,
+                        resolve_target: None,
+                        ops: Operations {
+                            load: LoadOp::Clear(default()),
+                            store: StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+                pass.set_render_pipeline(pipeline);
+                pass.set_bind_group(0, bind_group, &[]);
+                pass.draw(0..3, 0..1);
+            }
+        }
+        Ok(())
+    }
+}
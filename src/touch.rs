@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+use bevy_egui::egui;
+
+/// Decides which input method drives the egui pointer of a window when multiple methods interact
+/// with it, all methods are additionally forwarded as egui touches
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SpatialEguiPrimaryPointerPolicy {
+    /// The pointer stays with the method that pressed first until it releases
+    #[default]
+    FirstPressed,
+    /// Every new press takes over the pointer
+    LastPressed,
+}
+
+impl SpatialEguiPrimaryPointerPolicy {
+    /// `methods` are `(method, pressed, was_pressed)`, falls back to the previous primary method or
+    /// the first method if nothing is pressed
+    pub(crate) fn select(
+        &self,
+        last_primary: Option<Entity>,
+        methods: &[(Entity, bool, bool)],
+    ) -> Option<Entity> {
+        let last_primary = methods
+            .iter()
+            .find(|(method, _, _)| Some(*method) == last_primary);
+        let just_pressed = methods
+            .iter()
+            .find(|(_, pressed, was_pressed)| *pressed && !was_pressed);
+        let primary = match self {
+            SpatialEguiPrimaryPointerPolicy::FirstPressed => match last_primary {
+                Some((_, true, _)) => last_primary,
+                _ => just_pressed.or(last_primary),
+            },
+            SpatialEguiPrimaryPointerPolicy::LastPressed => just_pressed.or(last_primary),
+        };
+        primary.or(methods.first()).map(|(method, _, _)| *method)
+    }
+}
+
+pub(crate) fn touch_id(input_method: Entity) -> egui::TouchId {
+    egui::TouchId(input_method.to_bits())
+}
+
+pub(crate) fn touch_device_id(window: Entity) -> egui::TouchDeviceId {
+    egui::TouchDeviceId(window.to_bits())
+}
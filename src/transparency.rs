@@ -0,0 +1,89 @@
+use bevy::prelude::*;
+use bevy_egui::egui::{self, Pos2};
+
+use crate::{SpatialEguiWindow, WindowFrame};
+
+/// Only shows the egui content of a window, without the frame behind it. egui clears the texture
+/// to transparent, panels still draw their fill unless it's transparent too.
+///
+/// Windows with a [`crate::material::SpatialEguiWindowMaterial`] use the alpha mode of their
+/// material instead of `alpha_mode`.
+#[derive(Clone, Copy, Component, Debug)]
+pub struct SpatialEguiTransparentWindow {
+    /// How the transparent parts of the texture are drawn. egui renders with premultiplied alpha,
+    /// [`AlphaMode::AlphaToCoverage`] needs MSAA
+    pub alpha_mode: AlphaMode,
+    /// Input goes through the parts of the window egui didn't draw anything on, to what's behind
+    /// it. Areas, windows and panels count as content, a `CentralPanel` covers the whole space
+    /// the other panels leave
+    pub click_through: bool,
+}
+
+impl Default for SpatialEguiTransparentWindow {
+    fn default() -> Self {
+        Self {
+            alpha_mode: AlphaMode::Premultiplied,
+            click_through: true,
+        }
+    }
+}
+
+/// Whether egui drew something at `pos` last frame, like [`egui::Context::is_pointer_over_area`]
+/// for a position that doesn't have to be the pointer
+fn is_over_content(ctx: &egui::Context, pos: Pos2) -> bool {
+    // panels are on the background layer, the used rect includes them and a central panel
+    ctx.layer_id_at(pos)
+        .is_some_and(|layer| layer.order != egui::Order::Background)
+        || ctx.used_rect().contains(pos)
+}
+
+/// Whether input at `closest_point` goes through the window to what's behind it, `closest_point`
+/// and `location` are in window space
+pub(crate) fn passes_through(
+    transparent: &SpatialEguiTransparentWindow,
+    window: &SpatialEguiWindow,
+    egui_ctx: &egui::Context,
+    closest_point: Vec3,
+    location: &Transform,
+    is_pointer: bool,
+) -> bool {
+    if !transparent.click_through {
+        return false;
+    }
+    let hit = window
+        .shape
+        .hit(closest_point, location, is_pointer, window.size.z);
+    !window
+        .local_to_egui(window.unroll(hit))
+        .is_some_and(|pos| is_over_content(egui_ctx, pos))
+}
+
+pub(crate) fn hide_transparent_frames(
+    windows: Query<
+        (Has<SpatialEguiTransparentWindow>, &WindowFrame),
+        Or<(Changed<WindowFrame>, Added<SpatialEguiTransparentWindow>)>,
+    >,
+    all_frames: Query<&WindowFrame>,
+    mut removed: RemovedComponents<SpatialEguiTransparentWindow>,
+    mut visibilities: Query<&mut Visibility>,
+) {
+    let shown = removed
+        .read()
+        .filter_map(|window| all_frames.get(window).ok());
+    let changed = windows
+        .iter()
+        .map(|(transparent, frame)| (frame, transparent));
+    for (frame, transparent) in shown.map(|frame| (frame, false)).chain(changed) {
+        let Ok(mut visibility) = visibilities.get_mut(frame.0) else {
+            continue;
+        };
+        let next = if transparent {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+        if *visibility != next {
+            *visibility = next;
+        }
+    }
+}
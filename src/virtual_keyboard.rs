@@ -0,0 +1,208 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext, EguiInput};
+
+use crate::{
+    ImmovableSpatialEguiWindow, SpatialEguiFocus, SpatialEguiWindowConfig,
+    SpatialEguiWindowPhysicalSize, UnfocusableSpatialEguiWindow,
+};
+
+/// Shows an in-world keyboard below the focused spatial window while one of its widgets wants
+/// keyboard input
+pub struct SpatialEguiVirtualKeyboardPlugin;
+
+impl Plugin for SpatialEguiVirtualKeyboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpatialEguiVirtualKeyboardSettings>();
+        app.add_systems(Startup, spawn_virtual_keyboard);
+        // egui began the pass of the focused window before the keyboard is drawn, so keys reach
+        // it with the input of the next frame
+        app.add_systems(
+            Update,
+            (place_virtual_keyboard, draw_virtual_keyboard).chain(),
+        );
+    }
+}
+
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct SpatialEguiVirtualKeyboardSettings {
+    pub resolution: UVec2,
+    /// Physical height of the keyboard in meters
+    pub height: f32,
+    /// Space between the focused window and the keyboard in meters
+    pub gap: f32,
+}
+
+impl Default for SpatialEguiVirtualKeyboardSettings {
+    fn default() -> Self {
+        Self {
+            resolution: UVec2::new(1024, 320),
+            height: 0.2,
+            gap: 0.03,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, Default)]
+pub struct SpatialEguiVirtualKeyboard {
+    pub shift: bool,
+    /// Shows the symbols layer instead of the letters
+    pub symbols: bool,
+}
+
+const KEY_ROWS: [&str; 4] = ["1234567890", "qwertyuiop", "asdfghjkl", "zxcvbnm"];
+const SYMBOL_ROWS: [&str; 4] = ["1234567890", "@#$%&*-+()", "!\"':;/?_=", ",.~\\<>[]"];
+
+fn spawn_virtual_keyboard(settings: Res<SpatialEguiVirtualKeyboardSettings>, mut cmds: Commands) {
+    cmds.spawn((
+        SpatialEguiWindowConfig {
+            resolution: settings.resolution,
+            height: settings.height,
+            ..default()
+        },
+        SpatialEguiVirtualKeyboard::default(),
+        ImmovableSpatialEguiWindow,
+        UnfocusableSpatialEguiWindow,
+        SpatialBundle {
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+    ));
+}
+
+fn place_virtual_keyboard(
+    settings: Res<SpatialEguiVirtualKeyboardSettings>,
+    focus: Res<SpatialEguiFocus>,
+    mut windows: Query<
+        (
+            &mut EguiContext,
+            &GlobalTransform,
+            &SpatialEguiWindowPhysicalSize,
+        ),
+        Without<SpatialEguiVirtualKeyboard>,
+    >,
+    mut keyboards: Query<(&mut Transform, &mut Visibility), With<SpatialEguiVirtualKeyboard>>,
+) {
+    let mut target = None;
+    if let Some((mut egui_ctx, window_gt, window_size)) =
+        focus.window.and_then(|e| windows.get_mut(e).ok())
+    {
+        if egui_ctx.get_mut().wants_keyboard_input() {
+            target = Some((*window_gt, window_size.0));
+        }
+    }
+    for (mut transform, mut visibility) in &mut keyboards {
+        let Some((window_gt, window_size)) = target else {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        };
+        let (_, rotation, _) = window_gt.to_scale_rotation_translation();
+        let offset = (window_size.y / 2.0) + settings.gap + (settings.height / 2.0);
+        *transform = Transform::from_translation(window_gt.transform_point(Vec3::NEG_Y * offset))
+            .with_rotation(rotation);
+        visibility.set_if_neq(Visibility::Inherited);
+    }
+}
+
+fn draw_virtual_keyboard(
+    focus: Res<SpatialEguiFocus>,
+    mut keyboards: Query<(
+        &mut EguiContext,
+        &mut SpatialEguiVirtualKeyboard,
+        &Visibility,
+    )>,
+    mut windows: Query<&mut EguiInput, Without<SpatialEguiVirtualKeyboard>>,
+) {
+    for (mut egui_ctx, mut keyboard, visibility) in &mut keyboards {
+        if *visibility == Visibility::Hidden {
+            continue;
+        }
+        let mut events = Vec::new();
+        egui::CentralPanel::default().show(egui_ctx.get_mut(), |ui| {
+            let spacing = ui.spacing().item_spacing;
+            let key_size = egui::vec2(
+                (ui.available_width() - (spacing.x * 10.0)) / 11.0,
+                (ui.available_height() - (spacing.y * 4.0)) / 5.0,
+            );
+            let rows = match keyboard.symbols {
+                true => SYMBOL_ROWS,
+                false => KEY_ROWS,
+            };
+            for (i, row) in rows.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    if i == rows.len() - 1
+                        && !keyboard.symbols
+                        && ui
+                            .add_sized(
+                                key_size,
+                                egui::SelectableLabel::new(keyboard.shift, "Shift"),
+                            )
+                            .clicked()
+                    {
+                        keyboard.shift = !keyboard.shift;
+                    }
+                    for c in row.chars() {
+                        let c = if keyboard.shift {
+                            c.to_ascii_uppercase()
+                        } else {
+                            c
+                        };
+                        if ui
+                            .add_sized(key_size, egui::Button::new(c.to_string()))
+                            .clicked()
+                        {
+                            events.push(egui::Event::Text(c.to_string()));
+                            keyboard.shift = false;
+                        }
+                    }
+                    if i == 0 && ui.add_sized(key_size, egui::Button::new("Bksp")).clicked() {
+                        events.extend(key_events(egui::Key::Backspace));
+                    }
+                });
+            }
+            ui.horizontal(|ui| {
+                if ui.add_sized(key_size, egui::Button::new("<")).clicked() {
+                    events.extend(key_events(egui::Key::ArrowLeft));
+                }
+                if ui.add_sized(key_size, egui::Button::new(">")).clicked() {
+                    events.extend(key_events(egui::Key::ArrowRight));
+                }
+                let layer = match keyboard.symbols {
+                    true => "abc",
+                    false => "?123",
+                };
+                if ui.add_sized(key_size, egui::Button::new(layer)).clicked() {
+                    keyboard.symbols = !keyboard.symbols;
+                    keyboard.shift = false;
+                }
+                let space_size = egui::vec2((key_size.x * 5.0) + (spacing.x * 4.0), key_size.y);
+                if ui
+                    .add_sized(space_size, egui::Button::new("Space"))
+                    .clicked()
+                {
+                    events.push(egui::Event::Text(" ".to_string()));
+                }
+                let enter_size = egui::vec2((key_size.x * 3.0) + (spacing.x * 2.0), key_size.y);
+                if ui
+                    .add_sized(enter_size, egui::Button::new("Enter"))
+                    .clicked()
+                {
+                    events.extend(key_events(egui::Key::Enter));
+                }
+            });
+        });
+        if let Some(mut egui_input) = focus.window.and_then(|e| windows.get_mut(e).ok()) {
+            egui_input.events.extend(events);
+        }
+    }
+}
+
+/// A full key press, egui only needs the press but widgets may track the release
+fn key_events(key: egui::Key) -> [egui::Event; 2] {
+    [true, false].map(|pressed| egui::Event::Key {
+        key,
+        physical_key: None,
+        pressed,
+        repeat: false,
+        modifiers: egui::Modifiers::NONE,
+    })
+}
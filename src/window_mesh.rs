@@ -1,165 +1,417 @@
 use bevy::{
-    math::Vec2,
+    math::{Vec2, Vec3, Vec3Swizzles},
+    prelude::{Quat, Reflect, Transform},
     render::{mesh::Mesh, render_asset::RenderAssetUsages},
 };
 
+/// The shape of the egui side of a window
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+pub enum SpatialEguiWindowShape {
+    #[default]
+    Flat,
+    /// A section of a cylinder around a vertical axis in front of the egui side, like a dashboard
+    /// curving around the user. `radius` is the distance from the axis to the egui side and `arc`
+    /// is the angle the window spans in radians, the width of the window is `radius * arc`.
+    Cylinder { radius: f32, arc: f32 },
+}
+
+impl SpatialEguiWindowShape {
+    /// Maps a point in window space onto the unrolled egui side, which is what [`window_uv`] takes
+    pub fn unroll(&self, pos: Vec3, depth: f32) -> Vec2 {
+        match *self {
+            SpatialEguiWindowShape::Flat => pos.xy(),
+            SpatialEguiWindowShape::Cylinder { radius, .. } => {
+                let angle = pos.x.atan2(pos.z - cylinder_axis_z(radius, depth));
+                Vec2::new(angle * radius, pos.y)
+            }
+        }
+    }
+
+    /// Inverse of [`SpatialEguiWindowShape::unroll`], the point is on the egui side
+    pub fn roll(&self, pos: Vec2, depth: f32) -> Vec3 {
+        self.point(pos, depth, 0.0)
+    }
+
+    /// Point at `offset` meters behind the egui side
+    fn point(&self, pos: Vec2, depth: f32, offset: f32) -> Vec3 {
+        match *self {
+            SpatialEguiWindowShape::Flat => pos.extend((-depth / 2.0) + offset),
+            SpatialEguiWindowShape::Cylinder { radius, .. } => {
+                let angle = pos.x / radius;
+                let r = radius + offset;
+                Vec3::new(
+                    r * angle.sin(),
+                    pos.y,
+                    cylinder_axis_z(radius, depth) + (r * angle.cos()),
+                )
+            }
+        }
+    }
+
+    /// How far a point in window space is in front of the egui side, negative behind it
+    pub fn surface_distance(&self, pos: Vec3, depth: f32) -> f32 {
+        match *self {
+            SpatialEguiWindowShape::Flat => (-depth / 2.0) - pos.z,
+            SpatialEguiWindowShape::Cylinder { radius, .. } => {
+                let axis_z = cylinder_axis_z(radius, depth);
+                radius - Vec2::new(pos.x, pos.z - axis_z).length()
+            }
+        }
+    }
+
+    /// Size of the box around a window with the unrolled `size`
+    pub fn bounds(&self, size: Vec3) -> Vec3 {
+        match *self {
+            SpatialEguiWindowShape::Flat => size,
+            SpatialEguiWindowShape::Cylinder { radius, arc } => {
+                let half_arc = (arc / 2.0).min(std::f32::consts::FRAC_PI_2);
+                let sag = radius * (1.0 - (arc / 2.0).cos());
+                Vec3::new(
+                    2.0 * (radius + size.z) * half_arc.sin(),
+                    size.y,
+                    // the box is centered on the window, so it grows on both sides
+                    size.z + (2.0 * sag),
+                )
+            }
+        }
+    }
+
+    /// Where an input method hits the egui side, `closest_point` and `location` are in window space
+    pub(crate) fn hit(
+        &self,
+        closest_point: Vec3,
+        location: &Transform,
+        is_pointer: bool,
+        depth: f32,
+    ) -> Vec3 {
+        let SpatialEguiWindowShape::Cylinder { radius, arc } = *self else {
+            return closest_point;
+        };
+        if is_pointer {
+            let axis_z = cylinder_axis_z(radius, depth);
+            let hit = cylinder_hits(radius, depth, location)
+                .find(|hit| hit.x.atan2(hit.z - axis_z).abs() <= arc / 2.0);
+            if let Some(hit) = hit {
+                return hit;
+            }
+        }
+        self.roll(self.unroll(closest_point, depth), depth)
+    }
+
+    /// Where an input method points at on the unrolled egui side, also beyond the edges of the
+    /// window. `location` is in window space.
+    pub(crate) fn extended_hit(&self, location: &Transform, is_pointer: bool, depth: f32) -> Vec2 {
+        let point = match *self {
+            SpatialEguiWindowShape::Flat if is_pointer => {
+                // the egui side is the -Z face, where the hit the window is grabbed at is
+                let dir = location.forward();
+                if dir.z.abs() <= f32::EPSILON {
+                    location.translation
+                } else {
+                    let t = ((-depth / 2.0) - location.translation.z) / dir.z;
+                    location.translation + (dir * t)
+                }
+            }
+            SpatialEguiWindowShape::Cylinder { radius, .. } if is_pointer => {
+                cylinder_hits(radius, depth, location)
+                    .next()
+                    .unwrap_or(location.translation)
+            }
+            _ => location.translation,
+        };
+        self.unroll(point, depth)
+    }
+
+    /// Transform of a window of this shape whose unrolled egui side is moved by `offset`, it stays
+    /// on the same surface. Curved windows turn around the cylinder axis.
+    pub(crate) fn offset(&self, offset: Vec2, depth: f32) -> Transform {
+        match *self {
+            SpatialEguiWindowShape::Flat => Transform::from_translation(offset.extend(0.0)),
+            SpatialEguiWindowShape::Cylinder { radius, .. } => {
+                let axis = Vec3::new(0.0, 0.0, cylinder_axis_z(radius, depth));
+                let rotation = Quat::from_rotation_y(offset.x / radius);
+                Transform::from_translation(axis - (rotation * axis) + (Vec3::Y * offset.y))
+                    .with_rotation(rotation)
+            }
+        }
+    }
+
+    /// Direction of window space +X along the surface at `x` on the unrolled egui side
+    fn tangent(&self, x: f32) -> Vec3 {
+        match *self {
+            SpatialEguiWindowShape::Flat => Vec3::X,
+            SpatialEguiWindowShape::Cylinder { radius, .. } => {
+                let angle = x / radius;
+                Vec3::new(angle.cos(), 0.0, -angle.sin())
+            }
+        }
+    }
+
+    /// Normal of the egui side at `x` on the unrolled egui side
+    fn normal(&self, x: f32) -> Vec3 {
+        match *self {
+            SpatialEguiWindowShape::Flat => Vec3::NEG_Z,
+            SpatialEguiWindowShape::Cylinder { radius, .. } => {
+                let angle = x / radius;
+                -Vec3::new(angle.sin(), 0.0, angle.cos())
+            }
+        }
+    }
+}
+
+/// Where a pointer hits a cylinder around the axis of a curved window, nearest first. Only the
+/// half of the cylinder the window is on counts.
+fn cylinder_hits(radius: f32, depth: f32, location: &Transform) -> impl Iterator<Item = Vec3> {
+    let axis_z = cylinder_axis_z(radius, depth);
+    let origin = Vec2::new(location.translation.x, location.translation.z - axis_z);
+    let dir = location.forward();
+    let dir_2d = Vec2::new(dir.x, dir.z);
+    let a = dir_2d.length_squared();
+    let b = 2.0 * origin.dot(dir_2d);
+    let c = origin.length_squared() - (radius * radius);
+    let discriminant = (b * b) - (4.0 * a * c);
+    let hits = (a > f32::EPSILON && discriminant >= 0.0)
+        .then(|| [-1.0, 1.0].map(|sign| (-b + (sign * discriminant.sqrt())) / (2.0 * a)));
+    let translation = location.translation;
+    hits.into_iter()
+        .flatten()
+        .filter(|t| *t >= 0.0)
+        .map(move |t| translation + (dir * t))
+        .filter(move |hit| hit.z > axis_z)
+}
+
+/// The cylinder axis is in front of the egui side, the egui side touches the window's -Z face in
+/// its center
+fn cylinder_axis_z(radius: f32, depth: f32) -> f32 {
+    (-depth / 2.0) - radius
+}
+
+/// Texture coordinate of a point in window space. egui is shown on the -Z face, seen from there +X
+/// points left and +Y points up while the texture goes right and down.
+pub fn window_uv(pos: Vec2, size: Vec2) -> Vec2 {
+    Vec2::splat(0.5) - (pos / size)
+}
+
+/// Inverse of [`window_uv`]
+pub fn window_uv_to_local(uv: Vec2, size: Vec2) -> Vec2 {
+    (Vec2::splat(0.5) - uv) * size
+}
+
+/// How many segments a curved window is made of per radian
+const CURVE_SEGMENTS_PER_RADIAN: f32 = 12.0;
+
+/// Builds the meshes of a window, the egui side is a separate mesh from the frame around it so
+/// they can use different materials
 // Idea shamelessly copied from StardustXR flatland
-pub fn construct_window_mesh(size: Vec2, depth: f32) -> Mesh {
-    let mut mesh = Mesh::new(
-        bevy::render::mesh::PrimitiveTopology::TriangleList,
-        RenderAssetUsages::RENDER_WORLD,
-    );
-    let top_left_front = [
-        (size.x / 2.0) * -1.0,
-        (size.y / 2.0) * 1.0,
-        (depth / 2.0) * -1.0,
-    ];
-    let top_right_front = [
-        (size.x / 2.0) * 1.0,
-        (size.y / 2.0) * 1.0,
-        (depth / 2.0) * -1.0,
-    ];
-    let bottom_left_front = [
-        (size.x / 2.0) * -1.0,
-        (size.y / 2.0) * -1.0,
-        (depth / 2.0) * -1.0,
-    ];
-    let bottom_right_front = [
-        (size.x / 2.0) * 1.0,
-        (size.y / 2.0) * -1.0,
-        (depth / 2.0) * -1.0,
-    ];
-    let top_left_back = [
-        (size.x / 2.0) * -1.0,
-        (size.y / 2.0) * 1.0,
-        (depth / 2.0) * 1.0,
-    ];
-    let top_right_back = [
-        (size.x / 2.0) * 1.0,
-        (size.y / 2.0) * 1.0,
-        (depth / 2.0) * 1.0,
-    ];
-    let bottom_left_back = [
-        (size.x / 2.0) * -1.0,
-        (size.y / 2.0) * -1.0,
-        (depth / 2.0) * 1.0,
-    ];
-    let bottom_right_back = [
-        (size.x / 2.0) * 1.0,
-        (size.y / 2.0) * -1.0,
-        (depth / 2.0) * 1.0,
-    ];
-    mesh.insert_attribute(
-        Mesh::ATTRIBUTE_POSITION,
-        vec![
-            // front
-            top_left_front,
-            top_right_front,
-            bottom_left_front,
-            //
-            bottom_left_front,
-            top_right_front,
-            bottom_right_front,
-            // back
-            top_left_back,
-            bottom_left_back,
-            top_right_back,
-            //
-            bottom_left_back,
-            bottom_right_back,
-            top_right_back,
-            // top
-            top_left_back,
-            top_right_back,
-            top_left_front,
-            //
-            top_right_back,
-            top_right_front,
-            top_left_front,
-            // bottom
-            bottom_left_back,
-            bottom_left_front,
-            bottom_right_back,
-            //
-            bottom_right_back,
-            bottom_left_front,
-            bottom_right_front,
-            // left
-            top_left_back,
-            top_left_front,
-            bottom_left_back,
-            //
-            bottom_left_back,
-            top_left_front,
-            bottom_left_front,
-            // right
-            top_right_front,
-            top_right_back,
-            bottom_right_front,
-            //
-            bottom_right_front,
-            top_right_back,
-            bottom_right_back,
-        ],
-    );
-    // idk why i have to reverse left/right
-    let top_left = [1., 0.];
-    let top_right = [0., 0.];
-    let bottom_left = [1., 1.];
-    let bottom_right = [0., 1.];
-    mesh.insert_attribute(
-        Mesh::ATTRIBUTE_UV_0,
-        vec![
-            // front
-            top_left,
-            top_right,
-            bottom_left,
-            //
-            bottom_left,
-            top_right,
-            bottom_right,
+#[derive(Clone, Copy, Debug)]
+pub struct WindowMeshBuilder {
+    pub size: Vec2,
+    pub depth: f32,
+    pub shape: SpatialEguiWindowShape,
+    /// Radius of the rounded corners in meters, 0.0 for square corners
+    pub corner_radius: f32,
+    /// How many segments each rounded corner is made of
+    pub corner_segments: u32,
+}
+
+impl WindowMeshBuilder {
+    pub fn new(size: Vec2, depth: f32) -> Self {
+        Self {
+            size,
+            depth,
+            shape: SpatialEguiWindowShape::Flat,
+            corner_radius: 0.0,
+            corner_segments: 8,
+        }
+    }
+
+    pub fn with_shape(mut self, shape: SpatialEguiWindowShape) -> Self {
+        self.shape = shape;
+        self
+    }
+
+    pub fn with_rounded_corners(mut self, radius: f32, segments: u32) -> Self {
+        self.corner_radius = radius;
+        self.corner_segments = segments;
+        self
+    }
+
+    fn radius(&self) -> f32 {
+        self.corner_radius.clamp(0.0, self.size.min_element() / 2.0)
+    }
+
+    /// The x positions on the unrolled egui side the meshes are split at, from -X to +X
+    fn columns(&self) -> Vec<f32> {
+        let half_size = self.size / 2.0;
+        let radius = self.radius();
+        let inner = half_size.x - radius;
+        let corner_segments = if radius > 0.0 {
+            self.corner_segments.max(1)
+        } else {
+            0
+        };
+        let inner_segments = match self.shape {
+            SpatialEguiWindowShape::Flat => 1,
+            SpatialEguiWindowShape::Cylinder { radius, .. } => {
+                ((2.0 * inner / radius * CURVE_SEGMENTS_PER_RADIAN).ceil() as u32).max(1)
+            }
+        };
+        let corner = (0..corner_segments).map(|i| {
+            let angle = std::f32::consts::FRAC_PI_2 * (i as f32 / corner_segments as f32);
+            inner + (radius * angle.cos())
+        });
+        let mut columns = corner.clone().map(|x| -x).collect::<Vec<_>>();
+        columns.extend(
+            (0..=inner_segments)
+                .map(|i| -inner + (2.0 * inner * (i as f32 / inner_segments as f32))),
+        );
+        columns.extend(corner.rev());
+        columns
+    }
+
+    /// Outline of the egui side at `x`, the top y and the outward normal of the top edge in the
+    /// unrolled plane, the bottom is mirrored
+    fn outline(&self, x: f32) -> (f32, Vec2) {
+        let half_size = self.size / 2.0;
+        let radius = self.radius();
+        let into_corner = (x.abs() - (half_size.x - radius)).max(0.0);
+        if radius <= 0.0 || into_corner <= 0.0 {
+            return (half_size.y, Vec2::Y);
+        }
+        let rise = (radius.powi(2) - into_corner.powi(2)).max(0.0).sqrt();
+        let normal = Vec2::new(into_corner.copysign(x), rise) / radius;
+        (half_size.y - radius + rise, normal)
+    }
+
+    /// The egui side, the egui texture covers it like [`window_uv`] maps it. Rounded corners cut
+    /// the texture to the same shape.
+    pub fn content(&self) -> Mesh {
+        let columns = self.columns();
+        let mut positions = Vec::new();
+        let mut normals = Vec::new();
+        let mut tangents = Vec::new();
+        for pair in columns.windows(2) {
+            let [left, right] = [pair[0], pair[1]];
+            let (left_y, _) = self.outline(left);
+            let (right_y, _) = self.outline(right);
+            let top_left = Vec2::new(left, left_y);
+            let top_right = Vec2::new(right, right_y);
+            let bottom_left = Vec2::new(left, -left_y);
+            let bottom_right = Vec2::new(right, -right_y);
+            for pos in [
+                top_left,
+                top_right,
+                bottom_left,
+                //
+                bottom_left,
+                top_right,
+                bottom_right,
+            ] {
+                positions.push(self.shape.roll(pos, self.depth).to_array());
+                normals.push(self.shape.normal(pos.x).to_array());
+                // the texture goes toward -X and -Y
+                tangents.push((-self.shape.tangent(pos.x)).extend(-1.0).to_array());
+            }
+        }
+        let uvs = positions
+            .iter()
+            .map(|pos| window_uv(self.shape.unroll(Vec3::from(*pos), self.depth), self.size))
+            .map(Vec2::to_array)
+            .collect::<Vec<_>>();
+        let mut mesh = Mesh::new(
+            bevy::render::mesh::PrimitiveTopology::TriangleList,
+            RenderAssetUsages::RENDER_WORLD,
+        );
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_TANGENT, tangents);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        mesh
+    }
+
+    /// The back and edges of the window
+    pub fn frame(&self) -> Mesh {
+        let columns = self.columns();
+        let depth = self.depth;
+        let mut positions = Vec::new();
+        let mut normals = Vec::new();
+        let mut push = |pos: Vec2, offset: f32, normal: Vec3| {
+            positions.push(self.shape.point(pos, depth, offset).to_array());
+            normals.push(normal.to_array());
+        };
+        // outward normal of an edge in the unrolled plane
+        let edge_normal =
+            |x: f32, normal: Vec2| (self.shape.tangent(x) * normal.x) + (Vec3::Y * normal.y);
+        for pair in columns.windows(2) {
+            let [left, right] = [pair[0], pair[1]];
+            let (left_y, left_normal) = self.outline(left);
+            let (right_y, right_normal) = self.outline(right);
+            let top_left = Vec2::new(left, left_y);
+            let top_right = Vec2::new(right, right_y);
+            let bottom_left = Vec2::new(left, -left_y);
+            let bottom_right = Vec2::new(right, -right_y);
+            let back_left = -self.shape.normal(left);
+            let back_right = -self.shape.normal(right);
             // back
-            top_left,
-            bottom_left,
-            top_right,
+            push(top_left, depth, back_left);
+            push(bottom_left, depth, back_left);
+            push(top_right, depth, back_right);
             //
-            bottom_left,
-            bottom_right,
-            top_right,
+            push(bottom_left, depth, back_left);
+            push(bottom_right, depth, back_right);
+            push(top_right, depth, back_right);
             // top
-            top_left,
-            top_right,
-            top_left,
+            let top_left_normal = edge_normal(left, left_normal);
+            let top_right_normal = edge_normal(right, right_normal);
+            push(top_left, depth, top_left_normal);
+            push(top_right, depth, top_right_normal);
+            push(top_left, 0.0, top_left_normal);
             //
-            top_right,
-            top_right,
-            top_left,
+            push(top_right, depth, top_right_normal);
+            push(top_right, 0.0, top_right_normal);
+            push(top_left, 0.0, top_left_normal);
             // bottom
-            bottom_left,
-            bottom_left,
-            bottom_right,
-            //
-            bottom_right,
-            bottom_left,
-            bottom_right,
-            // left
-            top_left,
-            top_left,
-            bottom_left,
+            let bottom_left_normal = edge_normal(left, left_normal * Vec2::new(1.0, -1.0));
+            let bottom_right_normal = edge_normal(right, right_normal * Vec2::new(1.0, -1.0));
+            push(bottom_left, depth, bottom_left_normal);
+            push(bottom_left, 0.0, bottom_left_normal);
+            push(bottom_right, depth, bottom_right_normal);
             //
-            bottom_left,
-            top_left,
-            bottom_left,
-            // right
-            top_right,
-            top_right,
-            bottom_right,
-            //
-            bottom_right,
-            top_right,
-            bottom_right,
-        ],
-    );
-
-    mesh
+            push(bottom_right, depth, bottom_right_normal);
+            push(bottom_left, 0.0, bottom_left_normal);
+            push(bottom_right, 0.0, bottom_right_normal);
+        }
+        // the straight sides left between the corners
+        let left = columns[0];
+        let (left_y, _) = self.outline(left);
+        let left_normal = -self.shape.tangent(left);
+        let top_left = Vec2::new(left, left_y);
+        let bottom_left = Vec2::new(left, -left_y);
+        push(top_left, depth, left_normal);
+        push(top_left, 0.0, left_normal);
+        push(bottom_left, depth, left_normal);
+        //
+        push(bottom_left, depth, left_normal);
+        push(top_left, 0.0, left_normal);
+        push(bottom_left, 0.0, left_normal);
+        let right = columns[columns.len() - 1];
+        let (right_y, _) = self.outline(right);
+        let right_normal = self.shape.tangent(right);
+        let top_right = Vec2::new(right, right_y);
+        let bottom_right = Vec2::new(right, -right_y);
+        push(top_right, 0.0, right_normal);
+        push(top_right, depth, right_normal);
+        push(bottom_right, 0.0, right_normal);
+        //
+        push(bottom_right, 0.0, right_normal);
+        push(top_right, depth, right_normal);
+        push(bottom_right, depth, right_normal);
+        let mut mesh = Mesh::new(
+            bevy::render::mesh::PrimitiveTopology::TriangleList,
+            RenderAssetUsages::RENDER_WORLD,
+        );
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        mesh
+    }
 }
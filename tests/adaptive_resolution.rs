@@ -0,0 +1,68 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+use bevy_spatial_egui::resolution::SpatialEguiAdaptiveResolution;
+use common::{run_egui_pass, spawn_window, test_app};
+
+#[test]
+fn scale_follows_the_screen_size_within_bounds() {
+    let adaptive = SpatialEguiAdaptiveResolution::default();
+    let resolution = UVec2::new(800, 400);
+    assert_eq!(
+        adaptive.next_scale(resolution, Some(Vec2::new(1200.0, 600.0))),
+        1.5
+    );
+    assert_eq!(
+        adaptive.next_scale(resolution, Some(Vec2::new(8000.0, 4000.0))),
+        adaptive.max_scale
+    );
+    assert_eq!(
+        adaptive.next_scale(resolution, Some(Vec2::new(40.0, 20.0))),
+        adaptive.min_scale
+    );
+    // windows partly behind the camera count as infinitely large
+    assert_eq!(
+        adaptive.next_scale(resolution, Some(Vec2::INFINITY)),
+        adaptive.max_scale
+    );
+    // windows no camera sees use the smallest texture
+    assert_eq!(adaptive.next_scale(resolution, None), adaptive.min_scale);
+}
+
+#[test]
+fn small_changes_keep_the_texture() {
+    let adaptive = SpatialEguiAdaptiveResolution::default();
+    let resolution = UVec2::new(800, 400);
+    assert_eq!(
+        adaptive.next_scale(resolution, Some(Vec2::new(880.0, 440.0))),
+        1.0
+    );
+    assert_eq!(
+        adaptive.next_scale(resolution, Some(Vec2::new(640.0, 320.0))),
+        1.0
+    );
+    assert_eq!(
+        adaptive.next_scale(resolution, Some(Vec2::new(400.0, 200.0))),
+        0.5
+    );
+}
+
+#[test]
+fn egui_renders_at_the_scale_of_the_texture() {
+    let mut app = test_app();
+    let window = spawn_window(&mut app);
+    // no camera sees the window, it's scaled down as far as it goes
+    app.world_mut()
+        .entity_mut(window)
+        .insert(SpatialEguiAdaptiveResolution::default());
+    app.update();
+    app.update();
+    let ctx = run_egui_pass(&mut app, window);
+
+    let world = app.world();
+    let adaptive = world.get::<SpatialEguiAdaptiveResolution>(window).unwrap();
+    assert_eq!(adaptive.scale(), adaptive.min_scale);
+    assert_eq!(ctx.pixels_per_point(), adaptive.scale());
+    assert_eq!(ctx.screen_rect().size(), egui::vec2(512.0, 512.0));
+}
@@ -0,0 +1,163 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_spatial_egui::{
+    chrome::{
+        SpatialEguiChromeButtonPressed, SpatialEguiChromeElement, SpatialEguiChromeKind,
+        SpatialEguiWindowChrome,
+    },
+    simulated::SimulatedSpatialPointer,
+    ImmovableSpatialEguiWindow, SpatialEguiWindowConfig,
+};
+use common::{spawn_window, test_app};
+
+/// A 1x1 meter window with the default chrome, the bar is below it and the buttons on its right
+fn spawn_window_with_chrome(app: &mut App) -> Entity {
+    let window = spawn_window(app);
+    app.world_mut()
+        .entity_mut(window)
+        .insert(SpatialEguiWindowChrome::default());
+    // spawn the chrome, then propagate its transforms
+    app.update();
+    app.update();
+    window
+}
+
+fn chrome_element(app: &mut App, window: Entity, kind: SpatialEguiChromeKind) -> Entity {
+    app.world_mut()
+        .query::<(Entity, &SpatialEguiChromeElement)>()
+        .iter(app.world())
+        .find(|(_, element)| element.window == window && element.kind == kind)
+        .map(|(entity, _)| entity)
+        .unwrap()
+}
+
+/// A clicking pointer in front of the center of a chrome element
+fn click_element(app: &mut App, element: Entity) -> Entity {
+    let translation = app
+        .world()
+        .get::<GlobalTransform>(element)
+        .unwrap()
+        .translation();
+    let pointer = app
+        .world_mut()
+        .spawn((
+            SimulatedSpatialPointer {
+                click: true,
+                ..SimulatedSpatialPointer::new(element)
+            },
+            TransformBundle::from_transform(Transform::from_translation(translation - Vec3::Z)),
+        ))
+        .id();
+    app.update();
+    app.update();
+    pointer
+}
+
+fn move_pointer(app: &mut App, pointer: Entity, offset: Vec3) {
+    app.world_mut()
+        .get_mut::<Transform>(pointer)
+        .unwrap()
+        .translation += offset;
+    app.update();
+    app.update();
+}
+
+#[test]
+fn chrome_is_spawned_around_the_window() {
+    let mut app = test_app();
+    let window = spawn_window_with_chrome(&mut app);
+    for kind in [
+        SpatialEguiChromeKind::GrabBar,
+        SpatialEguiChromeKind::Close,
+        SpatialEguiChromeKind::Minimize,
+        SpatialEguiChromeKind::Pin,
+        SpatialEguiChromeKind::ResizeCorner,
+    ] {
+        let element = chrome_element(&mut app, window, kind);
+        assert_eq!(app.world().get::<Parent>(element).unwrap().get(), window);
+    }
+}
+
+#[test]
+fn the_close_button_despawns_the_window() {
+    let mut app = test_app();
+    let window = spawn_window_with_chrome(&mut app);
+    let close = chrome_element(&mut app, window, SpatialEguiChromeKind::Close);
+    click_element(&mut app, close);
+    assert!(app.world().get_entity(window).is_none());
+}
+
+#[test]
+fn buttons_pressed_by_several_pointers_are_pressed_once() {
+    let mut app = test_app();
+    let window = spawn_window_with_chrome(&mut app);
+    let pin = chrome_element(&mut app, window, SpatialEguiChromeKind::Pin);
+    let translation = app
+        .world()
+        .get::<GlobalTransform>(pin)
+        .unwrap()
+        .translation();
+    for offset in [-0.005, 0.005] {
+        app.world_mut().spawn((
+            SimulatedSpatialPointer {
+                click: true,
+                ..SimulatedSpatialPointer::new(pin)
+            },
+            TransformBundle::from_transform(Transform::from_translation(
+                translation + Vec3::new(offset, 0.0, -1.0),
+            )),
+        ));
+    }
+    app.update();
+    app.update();
+
+    let events = app
+        .world()
+        .resource::<Events<SpatialEguiChromeButtonPressed>>();
+    assert_eq!(events.get_reader().read(events).count(), 2);
+    // toggled once, not pinned and unpinned again
+    assert!(app
+        .world()
+        .get::<ImmovableSpatialEguiWindow>(window)
+        .is_some());
+}
+
+#[test]
+fn the_grab_bar_moves_the_window() {
+    let mut app = test_app();
+    let window = spawn_window_with_chrome(&mut app);
+    let bar = chrome_element(&mut app, window, SpatialEguiChromeKind::GrabBar);
+    let pointer = click_element(&mut app, bar);
+    move_pointer(&mut app, pointer, Vec3::new(0.2, 0.1, 0.0));
+
+    let translation = app.world().get::<Transform>(window).unwrap().translation;
+    assert!(
+        translation.distance(Vec3::new(0.2, 0.1, 0.0)) < 1e-4,
+        "{translation}"
+    );
+    // the chrome doesn't click the egui surface
+    assert_eq!(
+        app.world()
+            .get::<SpatialEguiWindowConfig>(window)
+            .unwrap()
+            .height,
+        1.0
+    );
+}
+
+#[test]
+fn the_resize_corner_resizes_the_window() {
+    let mut app = test_app();
+    let window = spawn_window_with_chrome(&mut app);
+    let corner = chrome_element(&mut app, window, SpatialEguiChromeKind::ResizeCorner);
+    let pointer = click_element(&mut app, corner);
+    move_pointer(&mut app, pointer, Vec3::new(-0.1, -0.1, 0.0));
+
+    let height = app
+        .world()
+        .get::<SpatialEguiWindowConfig>(window)
+        .unwrap()
+        .height;
+    assert!(height > 1.05, "{height}");
+}
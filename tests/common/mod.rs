@@ -0,0 +1,76 @@
+// every test binary only uses some of the helpers
+#![allow(dead_code)]
+
+use std::mem;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext, EguiInput};
+use bevy_spatial_egui::{
+    simulated::SimulatedSpatialPointer, SpatialEguiPlugin, SpatialEguiWindowConfig,
+};
+use bevy_suis::SuisCorePlugin;
+
+/// A headless app with the spatial egui plugin and the assets it needs
+pub fn test_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        TransformPlugin,
+        HierarchyPlugin,
+        SuisCorePlugin,
+        SpatialEguiPlugin,
+    ));
+    app.init_asset::<Image>()
+        .init_asset::<Mesh>()
+        .init_asset::<StandardMaterial>();
+    app
+}
+
+/// A 1x1 meter window at the origin with a 512x512 texture, facing -Z
+pub fn spawn_window(app: &mut App) -> Entity {
+    let window = app
+        .world_mut()
+        .spawn((
+            SpatialEguiWindowConfig::default(),
+            EguiContext::default(),
+            EguiInput::default(),
+            TransformBundle::default(),
+            // there is no visibility propagation without the render plugins
+            VisibilityBundle {
+                inherited_visibility: InheritedVisibility::VISIBLE,
+                ..default()
+            },
+        ))
+        .id();
+    app.update();
+    window
+}
+
+pub fn spawn_pointer(app: &mut App, window: Entity, pos: Vec3) -> Entity {
+    let pointer = app
+        .world_mut()
+        .spawn((
+            SimulatedSpatialPointer::new(window),
+            TransformBundle::from_transform(Transform::from_translation(pos)),
+        ))
+        .id();
+    // let the transform propagate before the pointer is read
+    app.update();
+    pointer
+}
+
+pub fn take_events(app: &mut App, window: Entity) -> Vec<egui::Event> {
+    let mut input = app.world_mut().get_mut::<EguiInput>(window).unwrap();
+    mem::take(&mut input.events)
+}
+
+/// Runs an empty egui pass of a window with its input, like bevy_egui does each frame, and
+/// returns its context
+pub fn run_egui_pass(app: &mut App, window: Entity) -> egui::Context {
+    let mut window = app.world_mut().entity_mut(window);
+    let raw_input = window.get_mut::<EguiInput>().unwrap().take();
+    let ctx = window.get_mut::<EguiContext>().unwrap().get_mut().clone();
+    let _ = ctx.run(raw_input, |_| {});
+    ctx
+}
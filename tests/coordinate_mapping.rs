@@ -0,0 +1,302 @@
+use bevy::{prelude::*, render::mesh::VertexAttributeValues};
+use bevy_egui::egui;
+use bevy_spatial_egui::{
+    window_mesh::{window_uv, SpatialEguiWindowShape, WindowMeshBuilder},
+    SpatialEguiWindow, SpatialEguiWindowConfig,
+};
+
+fn window(config: SpatialEguiWindowConfig) -> SpatialEguiWindow {
+    SpatialEguiWindow {
+        size: config.physical_size(),
+        egui_size: egui::vec2(config.resolution.x as f32, config.resolution.y as f32),
+        shape: config.surface_shape(),
+    }
+}
+
+fn assert_pos_eq(a: egui::Pos2, b: egui::Pos2) {
+    assert!((a - b).length() < 1e-3, "{a:?} != {b:?}");
+}
+
+#[test]
+fn physical_size_follows_the_resolution_aspect_ratio() {
+    let config = SpatialEguiWindowConfig {
+        resolution: UVec2::new(1024, 512),
+        height: 1.0,
+        ..default()
+    };
+    assert_eq!(config.physical_size().xy(), Vec2::new(2.0, 1.0));
+
+    let config = SpatialEguiWindowConfig {
+        resolution: UVec2::new(300, 600),
+        height: 0.5,
+        ..default()
+    };
+    assert_eq!(config.physical_size().xy(), Vec2::new(0.25, 0.5));
+}
+
+#[test]
+fn local_corners_map_to_egui_corners() {
+    let window = window(SpatialEguiWindowConfig {
+        resolution: UVec2::new(1024, 512),
+        height: 1.0,
+        ..default()
+    });
+    // seen from the -Z face +X is on the left
+    assert_pos_eq(
+        window.local_to_egui(Vec2::new(1.0, 0.5)).unwrap(),
+        egui::pos2(0.0, 0.0),
+    );
+    assert_pos_eq(
+        window.local_to_egui(Vec2::new(-1.0, -0.5)).unwrap(),
+        egui::pos2(1024.0, 512.0),
+    );
+    assert_pos_eq(
+        window.local_to_egui(Vec2::ZERO).unwrap(),
+        egui::pos2(512.0, 256.0),
+    );
+    assert_eq!(window.local_to_egui(Vec2::new(1.1, 0.0)), None);
+    assert_eq!(window.local_to_egui(Vec2::new(0.0, -0.6)), None);
+}
+
+#[test]
+fn egui_to_local_is_on_the_front_face() {
+    let window = window(SpatialEguiWindowConfig {
+        resolution: UVec2::new(512, 1024),
+        height: 2.0,
+        depth: 0.1,
+        ..default()
+    });
+    let local = window.egui_to_local(egui::pos2(128.0, 256.0));
+    assert!((local - Vec3::new(0.25, 0.5, -0.05)).length() < 1e-5);
+}
+
+#[test]
+fn world_mapping_round_trips_on_rotated_and_parented_windows() {
+    let window = window(SpatialEguiWindowConfig {
+        resolution: UVec2::new(800, 600),
+        height: 0.75,
+        ..default()
+    });
+    let parent = GlobalTransform::from(
+        Transform::from_xyz(1.0, 2.0, 3.0).with_rotation(Quat::from_rotation_y(1.0)),
+    );
+    let child = Transform::from_xyz(0.0, 0.5, -1.0)
+        .with_rotation(Quat::from_rotation_x(0.3) * Quat::from_rotation_z(0.2));
+    let window_gt = parent.mul_transform(child);
+
+    for pos in [
+        egui::pos2(0.0, 0.0),
+        egui::pos2(400.0, 300.0),
+        egui::pos2(799.0, 17.0),
+        egui::pos2(12.5, 599.0),
+    ] {
+        let world = window.egui_to_world(&window_gt, pos);
+        assert_pos_eq(window.world_to_egui(&window_gt, world).unwrap(), pos);
+    }
+
+    // points in front of the window project onto it
+    let front = window_gt.transform_point(Vec3::new(0.0, 0.0, -0.5));
+    assert_pos_eq(
+        window.world_to_egui(&window_gt, front).unwrap(),
+        egui::pos2(400.0, 300.0),
+    );
+    let outside = window_gt.transform_point(Vec3::new(2.0, 0.0, 0.0));
+    assert_eq!(window.world_to_egui(&window_gt, outside), None);
+}
+
+#[test]
+fn mesh_uvs_match_the_pointer_mapping() {
+    let size = Vec2::new(1.6, 0.9);
+    let mesh = WindowMeshBuilder::new(size, 0.05).content();
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        panic!("window mesh has no positions");
+    };
+    let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0) else {
+        panic!("window mesh has no uvs");
+    };
+    for (pos, uv) in positions.iter().zip(uvs) {
+        assert_eq!(Vec2::from(*uv), window_uv(Vec2::new(pos[0], pos[1]), size));
+    }
+    // the top left of the texture is at the top left of the -Z face when looking at it
+    let top_left = positions
+        .iter()
+        .zip(uvs)
+        .find(|(pos, _)| pos[2] < 0.0 && pos[0] > 0.0 && pos[1] > 0.0)
+        .unwrap();
+    assert_eq!(top_left.1, &[0.0, 0.0]);
+}
+
+#[test]
+fn cylinder_windows_map_along_their_surface() {
+    let shape = SpatialEguiWindowShape::Cylinder {
+        radius: 2.0,
+        arc: 1.0,
+    };
+    let window = window(SpatialEguiWindowConfig {
+        resolution: UVec2::new(1024, 512),
+        height: 1.0,
+        depth: 0.1,
+        shape,
+        ..default()
+    });
+    assert_eq!(window.size.xy(), Vec2::new(2.0, 1.0));
+
+    // the center touches the -Z face, the edges curve toward the viewer
+    let center = window.egui_to_local(egui::pos2(512.0, 256.0));
+    assert!((center - Vec3::new(0.0, 0.0, -0.05)).length() < 1e-5);
+    let edge = window.egui_to_local(egui::pos2(0.0, 0.0));
+    assert!(
+        (edge - Vec3::new(2.0 * 0.5f32.sin(), 0.5, -2.05 + (2.0 * 0.5f32.cos()))).length() < 1e-5
+    );
+
+    for pos in [
+        egui::pos2(0.0, 0.0),
+        egui::pos2(512.0, 256.0),
+        egui::pos2(1000.0, 17.0),
+        egui::pos2(12.5, 500.0),
+    ] {
+        let local = window.egui_to_local(pos);
+        assert_pos_eq(window.local_to_egui(window.unroll(local)).unwrap(), pos);
+    }
+
+    // points in front of the surface map to where they are radially
+    let inside = window.egui_to_local(egui::pos2(256.0, 128.0));
+    let towards_axis = inside + ((Vec3::new(0.0, inside.y, -2.05) - inside) * 0.5);
+    assert_pos_eq(
+        window.local_to_egui(window.unroll(towards_axis)).unwrap(),
+        egui::pos2(256.0, 128.0),
+    );
+}
+
+#[test]
+fn cylinder_windows_keep_the_aspect_ratio_of_their_resolution() {
+    let config = SpatialEguiWindowConfig {
+        resolution: UVec2::new(1024, 512),
+        height: 0.5,
+        shape: SpatialEguiWindowShape::Cylinder {
+            radius: 2.0,
+            arc: 3.0,
+        },
+        ..default()
+    };
+    assert_eq!(config.physical_size().xy(), Vec2::new(1.0, 0.5));
+    assert_eq!(
+        config.surface_shape(),
+        SpatialEguiWindowShape::Cylinder {
+            radius: 2.0,
+            arc: 0.5,
+        }
+    );
+}
+
+#[test]
+fn near_hands_in_front_of_cylinder_windows_are_not_touching() {
+    let shape = SpatialEguiWindowShape::Cylinder {
+        radius: 2.0,
+        arc: 1.0,
+    };
+    let window = window(SpatialEguiWindowConfig {
+        resolution: UVec2::new(1024, 512),
+        height: 1.0,
+        depth: 0.1,
+        shape,
+        ..default()
+    });
+    // in front of the middle of the window, but inside the box around its curve
+    let hand = Vec3::new(0.0, 0.0, -0.25);
+    let half_bounds = shape.bounds(window.size) / 2.0;
+    assert!(hand.abs().cmple(half_bounds).all());
+    assert!((window.surface_distance(hand) - 0.2).abs() < 1e-5);
+
+    // on the surface near the edge, and inside the window behind it
+    let edge = window.egui_to_local(egui::pos2(10.0, 256.0));
+    assert!(window.surface_distance(edge).abs() < 1e-5);
+    let behind = window.egui_to_local(egui::pos2(512.0, 256.0)) + Vec3::new(0.0, 0.0, 0.05);
+    assert!((window.surface_distance(behind) + 0.05).abs() < 1e-5);
+}
+
+#[test]
+fn cylinder_mesh_uvs_match_the_pointer_mapping() {
+    let shape = SpatialEguiWindowShape::Cylinder {
+        radius: 1.5,
+        arc: 2.0,
+    };
+    let size = Vec2::new(3.0, 0.9);
+    let mesh = WindowMeshBuilder::new(size, 0.05)
+        .with_shape(shape)
+        .content();
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        panic!("window mesh has no positions");
+    };
+    let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0) else {
+        panic!("window mesh has no uvs");
+    };
+    for (pos, uv) in positions.iter().zip(uvs) {
+        let uv = Vec2::from(*uv);
+        assert!(uv.cmpge(Vec2::splat(-1e-5)).all() && uv.cmple(Vec2::splat(1.0 + 1e-5)).all());
+        let expected = window_uv(shape.unroll(Vec3::from(*pos), 0.05), size);
+        assert!((uv - expected).length() < 1e-5);
+    }
+}
+
+fn positions_and_normals(mesh: &Mesh) -> Vec<(Vec3, Vec3)> {
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        panic!("window mesh has no positions");
+    };
+    let Some(VertexAttributeValues::Float32x3(normals)) = mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+    else {
+        panic!("window mesh has no normals");
+    };
+    positions
+        .iter()
+        .zip(normals)
+        .map(|(pos, normal)| (Vec3::from(*pos), Vec3::from(*normal)))
+        .collect()
+}
+
+#[test]
+fn window_meshes_have_outward_normals() {
+    let builder = WindowMeshBuilder::new(Vec2::new(1.6, 0.9), 0.05).with_rounded_corners(0.1, 4);
+    for (_, normal) in positions_and_normals(&builder.content()) {
+        assert_eq!(normal, Vec3::NEG_Z);
+    }
+    for (pos, normal) in positions_and_normals(&builder.frame()) {
+        assert!((normal.length() - 1.0).abs() < 1e-5);
+        assert!(
+            pos.dot(normal) > 0.0,
+            "{normal:?} points into the window at {pos:?}"
+        );
+    }
+}
+
+#[test]
+fn rounded_corners_cut_the_content() {
+    let size = Vec2::new(1.6, 0.9);
+    let radius = 0.2;
+    let mesh = WindowMeshBuilder::new(size, 0.05)
+        .with_rounded_corners(radius, 6)
+        .content();
+    let inner = (size / 2.0) - radius;
+    for (pos, _) in positions_and_normals(&mesh) {
+        let corner_distance = (pos.xy().abs() - inner).max(Vec2::ZERO).length();
+        assert!(
+            corner_distance <= radius + 1e-5,
+            "{pos:?} is outside the corners"
+        );
+    }
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        unreachable!();
+    };
+    // the corner itself is cut off
+    assert!(!positions
+        .iter()
+        .any(|pos| Vec2::new(pos[0], pos[1]).abs() == size / 2.0));
+}
@@ -0,0 +1,185 @@
+use bevy::prelude::*;
+use bevy_spatial_egui::{
+    layout::{
+        SpatialEguiLayout, SpatialEguiLayoutId, SpatialEguiLayoutPlugin, SpatialEguiWindowLayout,
+    },
+    minimize::MinimizedSpatialEguiWindow,
+    SpatialEguiWindowConfig,
+};
+
+fn test_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        TransformPlugin,
+        HierarchyPlugin,
+        SpatialEguiLayoutPlugin::default(),
+    ));
+    app
+}
+
+fn saved_window() -> SpatialEguiWindowLayout {
+    SpatialEguiWindowLayout {
+        transform: Transform::from_xyz(1.0, 2.0, 3.0).with_rotation(Quat::from_rotation_y(0.5)),
+        resolution: UVec2::new(800, 600),
+        height: 0.75,
+        parent: Some("anchor".to_string()),
+        visible: false,
+    }
+}
+
+fn spawn_window(app: &mut App, id: &str) -> Entity {
+    app.world_mut()
+        .spawn((
+            SpatialEguiLayoutId(id.to_string()),
+            SpatialEguiWindowConfig::default(),
+            SpatialBundle::default(),
+        ))
+        .id()
+}
+
+#[test]
+fn layouts_round_trip_through_ron() {
+    let mut layout = SpatialEguiLayout::default();
+    layout.windows.insert("main".to_string(), saved_window());
+    layout.save_preset("desk");
+
+    let loaded = SpatialEguiLayout::from_ron(&layout.to_ron().unwrap()).unwrap();
+    assert_eq!(loaded.windows, layout.windows);
+    assert_eq!(loaded.presets, layout.presets);
+}
+
+#[test]
+fn windows_are_restored_on_spawn() {
+    let mut app = test_app();
+    app.world_mut()
+        .resource_mut::<SpatialEguiLayout>()
+        .windows
+        .insert("main".to_string(), saved_window());
+    let anchor = app
+        .world_mut()
+        .spawn((
+            SpatialEguiLayoutId("anchor".to_string()),
+            SpatialBundle::default(),
+        ))
+        .id();
+    let window = spawn_window(&mut app, "main");
+    app.update();
+
+    let world = app.world();
+    assert_eq!(
+        *world.get::<Transform>(window).unwrap(),
+        saved_window().transform
+    );
+    let config = world.get::<SpatialEguiWindowConfig>(window).unwrap();
+    assert_eq!(config.resolution, UVec2::new(800, 600));
+    assert_eq!(config.height, 0.75);
+    assert_eq!(
+        *world.get::<Visibility>(window).unwrap(),
+        Visibility::Hidden
+    );
+    assert_eq!(world.get::<Parent>(window).unwrap().get(), anchor);
+}
+
+#[test]
+fn moved_windows_are_recorded_and_presets_move_them_back() {
+    let mut app = test_app();
+    let window = spawn_window(&mut app, "main");
+    app.update();
+    app.world_mut()
+        .resource_mut::<SpatialEguiLayout>()
+        .save_preset("start");
+
+    let moved = Transform::from_xyz(0.0, 1.0, -2.0);
+    *app.world_mut().get_mut::<Transform>(window).unwrap() = moved;
+    app.update();
+    assert_eq!(
+        app.world().resource::<SpatialEguiLayout>().windows["main"].transform,
+        moved
+    );
+
+    assert!(app
+        .world_mut()
+        .resource_mut::<SpatialEguiLayout>()
+        .apply_preset("start"));
+    app.update();
+    assert_eq!(
+        *app.world().get::<Transform>(window).unwrap(),
+        Transform::IDENTITY
+    );
+}
+
+#[test]
+fn windows_keep_their_parent_when_the_saved_one_is_missing() {
+    let mut app = test_app();
+    app.world_mut()
+        .resource_mut::<SpatialEguiLayout>()
+        .windows
+        .insert("main".to_string(), saved_window());
+    let app_parent = app.world_mut().spawn(SpatialBundle::default()).id();
+    let window = spawn_window(&mut app, "main");
+    app.world_mut().entity_mut(window).set_parent(app_parent);
+    app.update();
+
+    let world = app.world();
+    assert_eq!(world.get::<Parent>(window).unwrap().get(), app_parent);
+    assert_eq!(
+        *world.get::<Transform>(window).unwrap(),
+        Transform::IDENTITY
+    );
+    assert_eq!(
+        world.get::<SpatialEguiWindowConfig>(window).unwrap().height,
+        0.75
+    );
+}
+
+#[test]
+fn windows_wait_for_their_saved_parent_to_spawn() {
+    let mut app = test_app();
+    app.world_mut()
+        .resource_mut::<SpatialEguiLayout>()
+        .windows
+        .insert("main".to_string(), saved_window());
+    let window = spawn_window(&mut app, "main");
+    app.update();
+    assert!(app.world().get::<Parent>(window).is_none());
+    assert_eq!(
+        app.world().resource::<SpatialEguiLayout>().windows["main"],
+        saved_window()
+    );
+
+    let anchor = app
+        .world_mut()
+        .spawn((
+            SpatialEguiLayoutId("anchor".to_string()),
+            SpatialBundle::default(),
+        ))
+        .id();
+    app.update();
+    let world = app.world();
+    assert_eq!(world.get::<Parent>(window).unwrap().get(), anchor);
+    assert_eq!(
+        *world.get::<Transform>(window).unwrap(),
+        saved_window().transform
+    );
+}
+
+#[test]
+fn minimized_windows_keep_the_layout_from_before() {
+    let mut app = test_app();
+    let window = spawn_window(&mut app, "main");
+    app.update();
+    let before = app.world().resource::<SpatialEguiLayout>().windows["main"].clone();
+
+    // what minimizing does to the window
+    app.world_mut().entity_mut(window).insert((
+        MinimizedSpatialEguiWindow,
+        Transform::from_scale(Vec3::ZERO),
+        Visibility::Hidden,
+    ));
+    app.update();
+    assert_eq!(
+        app.world().resource::<SpatialEguiLayout>().windows["main"],
+        before
+    );
+}
@@ -0,0 +1,157 @@
+mod common;
+
+use bevy::{prelude::*, render::render_resource::AsBindGroup};
+use bevy_egui::{egui, EguiContext, EguiInput, EguiRenderToTextureHandle};
+use bevy_spatial_egui::{
+    material::{SpatialEguiMaterial, SpatialEguiMaterialPlugin, SpatialEguiWindowMaterial},
+    SpatialEguiWindowConfig,
+};
+use common::test_app;
+
+/// A material the crate doesn't know about
+#[derive(Asset, TypePath, AsBindGroup, Clone, Debug)]
+struct TintedEguiMaterial {
+    #[uniform(0)]
+    tint: LinearRgba,
+    #[texture(1)]
+    #[sampler(2)]
+    texture: Option<Handle<Image>>,
+}
+
+impl Material for TintedEguiMaterial {}
+
+impl SpatialEguiMaterial for TintedEguiMaterial {
+    fn egui_texture(&self) -> Option<&Handle<Image>> {
+        self.texture.as_ref()
+    }
+
+    fn set_egui_texture(&mut self, texture: Handle<Image>) {
+        self.texture = Some(texture);
+    }
+}
+
+fn window_material(app: &App, window: Entity) -> StandardMaterial {
+    let handle = app.world().get::<Handle<StandardMaterial>>(window).unwrap();
+    app.world()
+        .resource::<Assets<StandardMaterial>>()
+        .get(handle)
+        .unwrap()
+        .clone()
+}
+
+#[test]
+fn windows_use_a_copy_of_their_material_showing_egui() {
+    let mut app = test_app();
+    let window = app
+        .world_mut()
+        .spawn((
+            SpatialEguiWindowConfig {
+                unlit: false,
+                ..default()
+            },
+            SpatialEguiWindowMaterial(StandardMaterial {
+                emissive: LinearRgba::rgb(0.0, 0.5, 1.0),
+                ..default()
+            }),
+            EguiContext::default(),
+            EguiInput::default(),
+        ))
+        .id();
+    app.update();
+
+    let texture = app
+        .world()
+        .get::<EguiRenderToTextureHandle>(window)
+        .unwrap()
+        .0
+        .clone();
+    let material = window_material(&app, window);
+    assert_eq!(material.base_color_texture, Some(texture));
+    assert_eq!(material.emissive, LinearRgba::rgb(0.0, 0.5, 1.0));
+    assert!(!material.unlit);
+
+    app.world_mut()
+        .get_mut::<SpatialEguiWindowConfig>(window)
+        .unwrap()
+        .unlit = true;
+    app.update();
+    assert!(window_material(&app, window).unlit);
+}
+
+#[test]
+fn translucent_egui_visuals_blend_the_default_material() {
+    let mut app = test_app();
+    let mut egui_ctx = EguiContext::default();
+    let mut visuals = egui::Visuals::dark();
+    visuals.panel_fill = egui::Color32::from_black_alpha(128);
+    egui_ctx.get_mut().set_visuals(visuals);
+    let window = app
+        .world_mut()
+        .spawn((
+            SpatialEguiWindowConfig::default(),
+            egui_ctx,
+            EguiInput::default(),
+        ))
+        .id();
+    app.update();
+    assert_eq!(
+        window_material(&app, window).alpha_mode,
+        AlphaMode::Premultiplied
+    );
+
+    let opaque = app
+        .world_mut()
+        .spawn((
+            SpatialEguiWindowConfig::default(),
+            EguiContext::default(),
+            EguiInput::default(),
+        ))
+        .id();
+    app.update();
+    assert_eq!(window_material(&app, opaque).alpha_mode, AlphaMode::Opaque);
+}
+
+#[test]
+fn custom_materials_show_egui_through_their_plugin() {
+    let mut app = test_app();
+    app.add_plugins(SpatialEguiMaterialPlugin::<TintedEguiMaterial>::default())
+        .init_asset::<TintedEguiMaterial>();
+    let tint = LinearRgba::rgb(1.0, 0.5, 0.0);
+    let window = app
+        .world_mut()
+        .spawn((
+            SpatialEguiWindowConfig::default(),
+            SpatialEguiWindowMaterial(TintedEguiMaterial {
+                tint,
+                texture: None,
+            }),
+            EguiContext::default(),
+            EguiInput::default(),
+        ))
+        .id();
+    app.update();
+    app.update();
+
+    let texture = app
+        .world()
+        .get::<EguiRenderToTextureHandle>(window)
+        .unwrap()
+        .0
+        .clone();
+    let handle = app
+        .world()
+        .get::<Handle<TintedEguiMaterial>>(window)
+        .unwrap();
+    let material = app
+        .world()
+        .resource::<Assets<TintedEguiMaterial>>()
+        .get(handle)
+        .unwrap();
+    assert_eq!(material.texture, Some(texture));
+    assert_eq!(material.tint, tint);
+    // the window doesn't get the built in material on top
+    assert!(app
+        .world()
+        .get::<Handle<StandardMaterial>>(window)
+        .is_none());
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_egui::{EguiContext, EguiInput, EguiRenderToTextureHandle};
+use bevy_spatial_egui::{
+    minimize::{MinimizedSpatialEguiWindow, SpatialEguiMinimizeSettings, SpatialEguiWindowOrb},
+    repaint::PausedSpatialEguiTexture,
+    SpatialEguiWindowConfig,
+};
+use bevy_suis::Field;
+
+fn test_app() -> App {
+    let mut app = common::test_app();
+    app.insert_resource(SpatialEguiMinimizeSettings {
+        animation_duration: Duration::ZERO,
+        ..default()
+    });
+    app
+}
+
+fn orbs(app: &mut App) -> Vec<SpatialEguiWindowOrb> {
+    app.world_mut()
+        .query::<&SpatialEguiWindowOrb>()
+        .iter(app.world())
+        .copied()
+        .collect()
+}
+
+#[test]
+fn minimized_windows_turn_into_an_orb_and_back() {
+    let mut app = test_app();
+    let transform = Transform::from_xyz(1.0, 2.0, 3.0);
+    let window = app
+        .world_mut()
+        .spawn((
+            SpatialEguiWindowConfig::default(),
+            EguiContext::default(),
+            EguiInput::default(),
+            TransformBundle::from_transform(transform),
+            VisibilityBundle::default(),
+        ))
+        .id();
+    app.update();
+
+    app.world_mut()
+        .entity_mut(window)
+        .insert(MinimizedSpatialEguiWindow);
+    app.update();
+    app.update();
+    let world = app.world();
+    assert_eq!(
+        *world.get::<Visibility>(window).unwrap(),
+        Visibility::Hidden
+    );
+    assert!(world.get::<Field>(window).is_none());
+    // egui doesn't run a pass for it, bevy_egui keeps its render pass
+    assert!(world.get::<EguiContext>(window).is_none());
+    assert!(world.get::<PausedSpatialEguiTexture>(window).is_some());
+    assert!(world.get::<EguiRenderToTextureHandle>(window).is_some());
+    let window_orbs = orbs(&mut app);
+    assert_eq!(window_orbs.len(), 1);
+    assert_eq!(window_orbs[0].window, window);
+
+    app.world_mut()
+        .entity_mut(window)
+        .remove::<MinimizedSpatialEguiWindow>();
+    app.update();
+    app.update();
+    let world = app.world();
+    assert_eq!(
+        *world.get::<Visibility>(window).unwrap(),
+        Visibility::Inherited
+    );
+    assert!(world.get::<Field>(window).is_some());
+    assert!(world.get::<EguiContext>(window).is_some());
+    assert!(world.get::<PausedSpatialEguiTexture>(window).is_none());
+    assert_eq!(*world.get::<Transform>(window).unwrap(), transform);
+    assert!(orbs(&mut app).is_empty());
+}
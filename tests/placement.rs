@@ -0,0 +1,85 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_spatial_egui::placement::{FaceCamera, HeadLocked};
+use common::test_app;
+
+fn spawn(app: &mut App, transform: Transform) -> Entity {
+    app.world_mut()
+        .spawn(TransformBundle::from_transform(transform))
+        .id()
+}
+
+fn global(app: &App, entity: Entity) -> GlobalTransform {
+    *app.world().get::<GlobalTransform>(entity).unwrap()
+}
+
+#[test]
+fn face_camera_turns_the_egui_side_toward_the_camera() {
+    let mut app = test_app();
+    let camera = spawn(&mut app, Transform::from_xyz(3.0, 2.0, 0.0));
+    let parent = spawn(
+        &mut app,
+        Transform::from_xyz(0.0, 0.0, 1.0).with_rotation(Quat::from_rotation_y(1.0)),
+    );
+    let window = spawn(&mut app, Transform::IDENTITY);
+    app.world_mut()
+        .entity_mut(window)
+        .set_parent(parent)
+        .insert(FaceCamera::new(camera));
+    for _ in 0..3 {
+        app.update();
+    }
+
+    let window_gt = global(&app, window);
+    // the egui side is the -Z face
+    let to_camera = (Vec3::new(3.0, 0.0, -1.0)).normalize();
+    assert!(window_gt.forward().distance(to_camera) < 1e-4);
+    assert!(window_gt.translation().distance(Vec3::new(0.0, 0.0, 1.0)) < 1e-4);
+}
+
+#[test]
+fn rigid_head_locked_windows_stay_in_front_of_the_head() {
+    let mut app = test_app();
+    let head = spawn(
+        &mut app,
+        Transform::from_xyz(1.0, 1.5, 0.0).with_rotation(Quat::from_rotation_y(0.5)),
+    );
+    let window = spawn(&mut app, Transform::IDENTITY);
+    app.world_mut().entity_mut(window).insert(HeadLocked {
+        damping: 0.0,
+        ..HeadLocked::new(head)
+    });
+    for _ in 0..3 {
+        app.update();
+    }
+
+    let head_gt = global(&app, head);
+    let window_gt = global(&app, window);
+    let expected = head_gt.transform_point(Vec3::new(0.0, 0.0, -1.0));
+    assert!(window_gt.translation().distance(expected) < 1e-4);
+    // and face it
+    assert!(window_gt.forward().distance(*head_gt.back()) < 1e-4);
+}
+
+#[test]
+fn head_locked_windows_follow_the_head_in_the_same_frame() {
+    let mut app = test_app();
+    let head = spawn(&mut app, Transform::IDENTITY);
+    let window = spawn(&mut app, Transform::IDENTITY);
+    app.world_mut().entity_mut(window).insert(HeadLocked {
+        damping: 0.0,
+        ..HeadLocked::new(head)
+    });
+    app.update();
+
+    // moved like head tracking would, before PostUpdate
+    app.world_mut()
+        .get_mut::<Transform>(head)
+        .unwrap()
+        .translation = Vec3::new(2.0, 1.0, 0.0);
+    app.update();
+
+    let window_gt = global(&app, window);
+    assert!(window_gt.translation().distance(Vec3::new(2.0, 1.0, -1.0)) < 1e-4);
+}
@@ -0,0 +1,139 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use bevy::{
+    ecs::world::Command, log::LogPlugin, prelude::*, window::ExitCondition, winit::WinitPlugin,
+};
+use bevy_egui::{EguiContext, EguiPlugin, EguiRenderToTextureHandle};
+use bevy_spatial_egui::{
+    minimize::MinimizedSpatialEguiWindow,
+    repaint::{PausedSpatialEguiTexture, SpatialEguiRepaintPolicy},
+    texture::SpatialEguiTextureSettings,
+    SpatialEguiPlugin, SpatialEguiWindowConfig, SpawnSpatialEguiWindowCommand,
+};
+use bevy_suis::SuisCorePlugin;
+
+/// An app that renders, unlike the headless ones of the other tests. `None` if there is no GPU
+/// to render with.
+fn render_app() -> Option<App> {
+    // the render plugin panics when it finds no adapter
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        let mut app = App::new();
+        app.add_plugins((
+            DefaultPlugins
+                .set(WindowPlugin {
+                    primary_window: None,
+                    exit_condition: ExitCondition::DontExit,
+                    ..default()
+                })
+                .disable::<WinitPlugin>()
+                .disable::<LogPlugin>(),
+            EguiPlugin,
+            SuisCorePlugin,
+            SpatialEguiPlugin,
+        ));
+        app.finish();
+        app.cleanup();
+        app
+    }))
+    .ok()
+}
+
+fn spawn_window(app: &mut App) -> Entity {
+    let window = app.world_mut().spawn_empty().id();
+    SpawnSpatialEguiWindowCommand {
+        target_entity: Some(window),
+        resolution: UVec2::splat(256),
+        ..default()
+    }
+    .apply(app.world_mut());
+    app.update();
+    window
+}
+
+#[test]
+fn idle_windows_pause_and_resume_rendering() {
+    let Some(mut app) = render_app() else {
+        return;
+    };
+    let window = spawn_window(&mut app);
+    app.world_mut()
+        .entity_mut(window)
+        .insert(SpatialEguiRepaintPolicy::OnDemand);
+    for _ in 0..5 {
+        app.update();
+    }
+    assert!(app
+        .world()
+        .get::<PausedSpatialEguiTexture>(window)
+        .is_some());
+
+    app.world_mut()
+        .get_mut::<SpatialEguiWindowConfig>(window)
+        .unwrap()
+        .height = 2.0;
+    for _ in 0..5 {
+        app.update();
+    }
+    assert!(app
+        .world()
+        .get::<EguiRenderToTextureHandle>(window)
+        .is_some());
+}
+
+#[test]
+fn minimized_windows_restore_rendering() {
+    let Some(mut app) = render_app() else {
+        return;
+    };
+    let window = spawn_window(&mut app);
+    app.world_mut()
+        .entity_mut(window)
+        .insert(MinimizedSpatialEguiWindow);
+    for _ in 0..5 {
+        app.update();
+    }
+    assert!(app.world().get::<EguiContext>(window).is_none());
+
+    app.world_mut()
+        .entity_mut(window)
+        .remove::<MinimizedSpatialEguiWindow>();
+    for _ in 0..5 {
+        app.update();
+    }
+    assert!(app.world().get::<EguiContext>(window).is_some());
+    assert!(app
+        .world()
+        .get::<PausedSpatialEguiTexture>(window)
+        .is_none());
+}
+
+#[test]
+fn mipmapped_windows_pause_and_resume_rendering() {
+    let Some(mut app) = render_app() else {
+        return;
+    };
+    let window = spawn_window(&mut app);
+    app.world_mut().entity_mut(window).insert((
+        SpatialEguiTextureSettings::default(),
+        SpatialEguiRepaintPolicy::OnDemand,
+    ));
+    for _ in 0..5 {
+        app.update();
+    }
+    assert!(app
+        .world()
+        .get::<PausedSpatialEguiTexture>(window)
+        .is_some());
+
+    app.world_mut()
+        .get_mut::<SpatialEguiWindowConfig>(window)
+        .unwrap()
+        .height = 2.0;
+    for _ in 0..5 {
+        app.update();
+    }
+    assert!(app
+        .world()
+        .get::<EguiRenderToTextureHandle>(window)
+        .is_some());
+}
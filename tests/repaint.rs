@@ -0,0 +1,119 @@
+mod common;
+
+use std::mem;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext, EguiInput, EguiRenderToTextureHandle};
+use bevy_spatial_egui::{
+    repaint::{PausedSpatialEguiTexture, SpatialEguiRepaintPolicy},
+    SpatialEguiWindowConfig,
+};
+use common::{spawn_pointer, spawn_window, take_events};
+
+fn test_app() -> App {
+    let mut app = common::test_app();
+    // stands in for the ui of the app, which bevy_egui would run in its frame
+    app.add_systems(
+        Update,
+        |mut contexts: Query<(&mut EguiContext, &mut EguiInput)>| {
+            for (mut ctx, mut input) in &mut contexts {
+                let raw_input = egui::RawInput {
+                    events: mem::take(&mut input.events),
+                    ..default()
+                };
+                let _ = ctx.get_mut().run(raw_input, |_| {});
+            }
+        },
+    );
+    app
+}
+
+#[test]
+fn idle_windows_stop_rendering_until_they_change() {
+    let mut app = test_app();
+    let window = app
+        .world_mut()
+        .spawn((
+            SpatialEguiWindowConfig::default(),
+            SpatialEguiRepaintPolicy::OnDemand,
+            EguiContext::default(),
+            EguiInput::default(),
+        ))
+        .id();
+    for _ in 0..5 {
+        app.update();
+    }
+    assert!(!is_rendering(&app, window));
+    // bevy_egui would set up the render pass again for a new handle
+    assert!(app
+        .world()
+        .get::<EguiRenderToTextureHandle>(window)
+        .is_some());
+
+    app.world_mut()
+        .get_mut::<SpatialEguiWindowConfig>(window)
+        .unwrap()
+        .height = 2.0;
+    app.update();
+    assert!(is_rendering(&app, window));
+}
+
+#[test]
+fn windows_without_a_policy_always_render() {
+    let mut app = test_app();
+    let window = app
+        .world_mut()
+        .spawn((
+            SpatialEguiWindowConfig::default(),
+            EguiContext::default(),
+            EguiInput::default(),
+        ))
+        .id();
+    for _ in 0..5 {
+        app.update();
+    }
+    assert!(is_rendering(&app, window));
+}
+
+fn is_rendering(app: &App, window: Entity) -> bool {
+    app.world()
+        .get::<PausedSpatialEguiTexture>(window)
+        .is_none()
+}
+
+#[test]
+fn pointer_input_wakes_idle_windows() {
+    let mut app = test_app();
+    let window = spawn_window(&mut app);
+    app.world_mut()
+        .entity_mut(window)
+        .insert(SpatialEguiRepaintPolicy::OnDemand);
+    for _ in 0..5 {
+        app.update();
+    }
+    assert!(!is_rendering(&app, window));
+
+    spawn_pointer(&mut app, window, Vec3::new(0.25, 0.25, -1.0));
+    app.update();
+    assert!(is_rendering(&app, window));
+}
+
+#[test]
+fn repeated_pointer_gone_events_let_windows_idle() {
+    let mut app = test_app();
+    let window = spawn_window(&mut app);
+    app.world_mut()
+        .entity_mut(window)
+        .insert(SpatialEguiRepaintPolicy::OnDemand);
+    let pointer = spawn_pointer(&mut app, window, Vec3::new(0.25, 0.25, -1.0));
+    app.update();
+    assert!(is_rendering(&app, window));
+
+    app.world_mut().despawn(pointer);
+    for _ in 0..10 {
+        app.update();
+    }
+    // only the first `PointerGone` reaches egui, which requests a repaint for every event
+    assert!(take_events(&mut app, window).is_empty());
+    assert!(!is_rendering(&app, window));
+}
@@ -0,0 +1,395 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+use bevy_spatial_egui::{
+    events::{
+        SpatialEguiWindowGrabbed, SpatialEguiWindowHovered, SpatialEguiWindowMoved,
+        SpatialEguiWindowReleased,
+    },
+    grab::SpatialEguiGrabSettings,
+    haptics::{SpatialEguiHapticsPlugin, SpatialEguiHapticsRecorder, SpatialEguiHapticsSettings},
+    resize::SpatialEguiWindowResizable,
+    simulated::SimulatedSpatialPointer,
+    snap::SpatialEguiSnapSettings,
+    SpatialEguiWindowConfig,
+};
+use common::{spawn_pointer, spawn_window, take_events, test_app};
+
+fn set_click(app: &mut App, pointer: Entity, click: bool) {
+    app.world_mut()
+        .get_mut::<SimulatedSpatialPointer>(pointer)
+        .unwrap()
+        .click = click;
+}
+
+fn set_grab(app: &mut App, pointer: Entity, grab: bool) {
+    app.world_mut()
+        .get_mut::<SimulatedSpatialPointer>(pointer)
+        .unwrap()
+        .grab = grab;
+    app.update();
+}
+
+fn sent_events<E: Event + Clone>(app: &App) -> Vec<E> {
+    let events = app.world().resource::<Events<E>>();
+    events.get_reader().read(events).cloned().collect()
+}
+
+#[test]
+fn hovering_moves_the_pointer() {
+    let mut app = test_app();
+    let window = spawn_window(&mut app);
+    spawn_pointer(&mut app, window, Vec3::new(0.25, 0.25, -1.0));
+    take_events(&mut app, window);
+
+    app.update();
+    let events = take_events(&mut app, window);
+    assert!(events.contains(&egui::Event::PointerMoved(egui::pos2(128.0, 128.0))));
+    assert!(!events.contains(&egui::Event::PointerGone));
+}
+
+#[test]
+fn clicking_presses_and_releases_the_primary_button() {
+    let mut app = test_app();
+    let window = spawn_window(&mut app);
+    let pointer = spawn_pointer(&mut app, window, Vec3::new(0.0, 0.0, -1.0));
+    take_events(&mut app, window);
+
+    set_click(&mut app, pointer, true);
+    app.update();
+    let pressed = egui::Event::PointerButton {
+        pos: egui::pos2(256.0, 256.0),
+        button: egui::PointerButton::Primary,
+        pressed: true,
+        modifiers: egui::Modifiers::NONE,
+    };
+    assert!(take_events(&mut app, window).contains(&pressed));
+
+    set_click(&mut app, pointer, false);
+    app.update();
+    let released = egui::Event::PointerButton {
+        pressed: false,
+        ..pressed
+    };
+    assert!(take_events(&mut app, window).contains(&released));
+}
+
+#[test]
+fn despawning_a_pressed_pointer_releases_the_button() {
+    let mut app = test_app();
+    let window = spawn_window(&mut app);
+    let pointer = spawn_pointer(&mut app, window, Vec3::new(0.0, 0.0, -1.0));
+    set_click(&mut app, pointer, true);
+    app.update();
+    take_events(&mut app, window);
+
+    app.world_mut().despawn(pointer);
+    app.update();
+    let events = take_events(&mut app, window);
+    assert!(events.iter().any(|e| matches!(
+        e,
+        egui::Event::PointerButton {
+            button: egui::PointerButton::Primary,
+            pressed: false,
+            ..
+        }
+    )));
+    assert!(events.contains(&egui::Event::PointerGone));
+}
+
+#[test]
+fn pointers_outside_the_window_are_ignored() {
+    let mut app = test_app();
+    let window = spawn_window(&mut app);
+    spawn_pointer(&mut app, window, Vec3::new(2.0, 0.0, -1.0));
+    take_events(&mut app, window);
+
+    app.update();
+    assert_eq!(
+        take_events(&mut app, window),
+        vec![egui::Event::PointerGone]
+    );
+}
+
+#[test]
+fn multiple_pointers_become_touches() {
+    let mut app = test_app();
+    let window = spawn_window(&mut app);
+    let a = spawn_pointer(&mut app, window, Vec3::new(0.25, 0.0, -1.0));
+    let b = spawn_pointer(&mut app, window, Vec3::new(-0.25, 0.0, -1.0));
+    take_events(&mut app, window);
+
+    set_click(&mut app, a, true);
+    set_click(&mut app, b, true);
+    app.update();
+    let events = take_events(&mut app, window);
+    let touch_ids = events
+        .iter()
+        .filter_map(|e| match e {
+            egui::Event::Touch {
+                id,
+                phase: egui::TouchPhase::Start,
+                ..
+            } => Some(*id),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(touch_ids.len(), 2);
+    assert_ne!(touch_ids[0], touch_ids[1]);
+    let pointer_moves = events
+        .iter()
+        .filter(|e| matches!(e, egui::Event::PointerMoved(_)))
+        .count();
+    assert_eq!(pointer_moves, 1);
+}
+
+#[test]
+fn grabbing_sends_lifecycle_events() {
+    let mut app = test_app();
+    let window = spawn_window(&mut app);
+    let pointer = spawn_pointer(&mut app, window, Vec3::new(0.0, 0.0, -1.0));
+    let hovered = sent_events::<SpatialEguiWindowHovered>(&app);
+    assert_eq!(hovered.len(), 1);
+    assert_eq!(hovered[0].input_method, pointer);
+
+    app.world_mut()
+        .get_mut::<SimulatedSpatialPointer>(pointer)
+        .unwrap()
+        .grab = true;
+    app.update();
+    let grabbed = sent_events::<SpatialEguiWindowGrabbed>(&app);
+    assert_eq!(grabbed.len(), 1);
+    assert_eq!(grabbed[0].window, window);
+    assert_eq!(grabbed[0].input_method, pointer);
+
+    app.world_mut()
+        .get_mut::<SimulatedSpatialPointer>(pointer)
+        .unwrap()
+        .grab = false;
+    app.update();
+    let released = sent_events::<SpatialEguiWindowReleased>(&app);
+    assert_eq!(released.len(), 1);
+    assert_eq!(released[0].input_method, pointer);
+}
+
+#[test]
+fn windows_held_still_are_not_moved() {
+    let mut app = test_app();
+    let window = spawn_window(&mut app);
+    let pointer = spawn_pointer(&mut app, window, Vec3::new(0.0, 0.0, -1.0));
+    set_grab(&mut app, pointer, true);
+    app.update();
+    app.update();
+    assert!(sent_events::<SpatialEguiWindowMoved>(&app).is_empty());
+
+    app.world_mut()
+        .get_mut::<Transform>(pointer)
+        .unwrap()
+        .translation
+        .x = 0.1;
+    app.update();
+    app.update();
+    assert!(!sent_events::<SpatialEguiWindowMoved>(&app).is_empty());
+}
+
+#[test]
+fn grabbing_plays_haptic_pulses() {
+    let mut app = test_app();
+    app.add_plugins(SpatialEguiHapticsPlugin::<SpatialEguiHapticsRecorder>::default());
+    let settings = *app.world().resource::<SpatialEguiHapticsSettings>();
+    let window = spawn_window(&mut app);
+    let pointer = spawn_pointer(&mut app, window, Vec3::new(0.0, 0.0, -1.0));
+    app.world_mut()
+        .get_mut::<SimulatedSpatialPointer>(pointer)
+        .unwrap()
+        .grab = true;
+    app.update();
+
+    let pulses = &app.world().resource::<SpatialEguiHapticsRecorder>().pulses;
+    assert_eq!(
+        pulses,
+        &vec![
+            (pointer, settings.hover.unwrap()),
+            (pointer, settings.grab.unwrap())
+        ]
+    );
+}
+
+#[test]
+fn two_handed_grabs_scale_the_window() {
+    let mut app = test_app();
+    let window = spawn_window(&mut app);
+    app.world_mut()
+        .entity_mut(window)
+        .insert(SpatialEguiGrabSettings {
+            two_handed_scale: true,
+            ..default()
+        });
+    let a = spawn_pointer(&mut app, window, Vec3::new(0.2, 0.0, -1.0));
+    let b = spawn_pointer(&mut app, window, Vec3::new(-0.2, 0.0, -1.0));
+    for pointer in [a, b] {
+        app.world_mut()
+            .get_mut::<SimulatedSpatialPointer>(pointer)
+            .unwrap()
+            .grab = true;
+        app.update();
+    }
+    assert!(sent_events::<SpatialEguiWindowGrabbed>(&app)
+        .iter()
+        .any(|e| e.input_method == b));
+
+    app.world_mut()
+        .get_mut::<Transform>(b)
+        .unwrap()
+        .translation
+        .x = -0.4;
+    app.update();
+    app.update();
+    let height = app
+        .world()
+        .get::<SpatialEguiWindowConfig>(window)
+        .unwrap()
+        .height;
+    assert!((height - 1.5).abs() < 1e-4, "{height}");
+}
+
+#[test]
+fn two_handed_grabs_of_windows_that_are_not_resizable_keep_a_minimum_size() {
+    let mut app = test_app();
+    let window = spawn_window(&mut app);
+    app.world_mut()
+        .entity_mut(window)
+        .insert(SpatialEguiGrabSettings {
+            two_handed_scale: true,
+            ..default()
+        });
+    let a = spawn_pointer(&mut app, window, Vec3::new(0.2, 0.0, -1.0));
+    let b = spawn_pointer(&mut app, window, Vec3::new(-0.2, 0.0, -1.0));
+    for pointer in [a, b] {
+        set_grab(&mut app, pointer, true);
+    }
+
+    app.world_mut()
+        .get_mut::<Transform>(b)
+        .unwrap()
+        .translation
+        .x = 0.199;
+    app.update();
+    app.update();
+    let height = app
+        .world()
+        .get::<SpatialEguiWindowConfig>(window)
+        .unwrap()
+        .height;
+    let min_height = SpatialEguiWindowResizable::default().min_size.y;
+    assert!((height - min_height).abs() < 1e-4, "{height}");
+}
+
+#[test]
+fn two_handed_grabs_of_very_wide_windows_keep_them_within_bounds() {
+    let mut app = test_app();
+    let window = spawn_window(&mut app);
+    app.world_mut().entity_mut(window).insert((
+        SpatialEguiGrabSettings {
+            two_handed_scale: true,
+            ..default()
+        },
+        SpatialEguiWindowResizable::default(),
+    ));
+    // 60 meters wide, so no height fits both the minimum and maximum size
+    app.world_mut()
+        .get_mut::<SpatialEguiWindowConfig>(window)
+        .unwrap()
+        .resolution = UVec2::new(6000, 100);
+    app.update();
+    let a = spawn_pointer(&mut app, window, Vec3::new(0.2, 0.0, -1.0));
+    let b = spawn_pointer(&mut app, window, Vec3::new(-0.2, 0.0, -1.0));
+    for pointer in [a, b] {
+        app.world_mut()
+            .get_mut::<SimulatedSpatialPointer>(pointer)
+            .unwrap()
+            .grab = true;
+        app.update();
+    }
+    app.world_mut()
+        .get_mut::<Transform>(b)
+        .unwrap()
+        .translation
+        .x = -0.4;
+    app.update();
+    app.update();
+    let height = app
+        .world()
+        .get::<SpatialEguiWindowConfig>(window)
+        .unwrap()
+        .height;
+    assert!((height - (5.0 / 60.0)).abs() < 1e-4, "{height}");
+}
+
+#[test]
+fn only_the_pointer_resizing_a_window_drives_the_resize() {
+    let mut app = test_app();
+    let window = spawn_window(&mut app);
+    app.world_mut()
+        .entity_mut(window)
+        .insert(SpatialEguiWindowResizable::default());
+    // on the resize handle of the +X edge
+    let resizing = spawn_pointer(&mut app, window, Vec3::new(0.48, 0.0, -1.0));
+    set_grab(&mut app, resizing, true);
+    let other = spawn_pointer(&mut app, window, Vec3::new(0.0, 0.0, -1.0));
+    set_grab(&mut app, other, true);
+    set_grab(&mut app, other, false);
+
+    app.world_mut()
+        .get_mut::<Transform>(resizing)
+        .unwrap()
+        .translation
+        .x = 0.43;
+    app.update();
+    app.update();
+    let resolution = app
+        .world()
+        .get::<SpatialEguiWindowConfig>(window)
+        .unwrap()
+        .resolution;
+    assert_eq!(resolution, UVec2::new(486, 512));
+}
+
+#[test]
+fn released_windows_snap_to_the_grid() {
+    let mut app = test_app();
+    let window = spawn_window(&mut app);
+    app.world_mut()
+        .entity_mut(window)
+        .insert(SpatialEguiSnapSettings {
+            grid: Some(0.5),
+            window_edges: None,
+            ..default()
+        });
+    let pointer = spawn_pointer(&mut app, window, Vec3::new(0.0, 0.0, -1.0));
+    app.world_mut()
+        .get_mut::<SimulatedSpatialPointer>(pointer)
+        .unwrap()
+        .grab = true;
+    app.update();
+
+    app.world_mut()
+        .get_mut::<Transform>(pointer)
+        .unwrap()
+        .translation
+        .x = 0.35;
+    app.update();
+    app.update();
+    app.world_mut()
+        .get_mut::<SimulatedSpatialPointer>(pointer)
+        .unwrap()
+        .grab = false;
+    app.update();
+
+    let translation = app.world().get::<Transform>(window).unwrap().translation;
+    assert!(
+        translation.distance(Vec3::new(0.5, 0.0, 0.0)) < 1e-4,
+        "{translation}"
+    );
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext, EguiInput, EguiRenderToTextureHandle};
+use bevy_spatial_egui::{texture::SpatialEguiTextureSettings, SpatialEguiWindowConfig};
+use common::{run_egui_pass, test_app};
+
+#[test]
+fn windows_show_a_mipmapped_supersampled_texture() {
+    let mut app = test_app();
+    let window = app
+        .world_mut()
+        .spawn((
+            SpatialEguiWindowConfig {
+                resolution: UVec2::new(256, 128),
+                ..default()
+            },
+            SpatialEguiTextureSettings {
+                supersampling: 2.0,
+                ..default()
+            },
+            EguiContext::default(),
+            EguiInput::default(),
+        ))
+        .id();
+    app.update();
+    app.update();
+    let ctx = run_egui_pass(&mut app, window);
+
+    let world = app.world();
+    assert_eq!(ctx.pixels_per_point(), 2.0);
+    assert_eq!(ctx.screen_rect().size(), egui::vec2(256.0, 128.0));
+    let source = world
+        .get::<EguiRenderToTextureHandle>(window)
+        .unwrap()
+        .0
+        .clone();
+    let material = world.get::<Handle<StandardMaterial>>(window).unwrap();
+    let shown = world
+        .resource::<Assets<StandardMaterial>>()
+        .get(material)
+        .unwrap()
+        .base_color_texture
+        .clone()
+        .unwrap();
+    assert_ne!(shown, source);
+    let images = world.resource::<Assets<Image>>();
+    let source = images.get(&source).unwrap();
+    assert_eq!(source.size(), UVec2::new(512, 256));
+    let shown = images.get(&shown).unwrap();
+    assert_eq!(shown.size(), UVec2::new(512, 256));
+    assert_eq!(shown.texture_descriptor.mip_level_count, 10);
+    assert_eq!(
+        shown.texture_descriptor.format,
+        source.texture_descriptor.format
+    );
+}
+
+#[test]
+fn turning_mipmaps_off_shows_the_egui_texture() {
+    let mut app = test_app();
+    let window = app
+        .world_mut()
+        .spawn((
+            SpatialEguiWindowConfig::default(),
+            SpatialEguiTextureSettings::default(),
+            EguiContext::default(),
+            EguiInput::default(),
+        ))
+        .id();
+    app.update();
+    app.world_mut()
+        .get_mut::<SpatialEguiTextureSettings>(window)
+        .unwrap()
+        .mipmaps = false;
+    app.update();
+
+    let world = app.world();
+    let source = &world.get::<EguiRenderToTextureHandle>(window).unwrap().0;
+    let material = world.get::<Handle<StandardMaterial>>(window).unwrap();
+    let shown = &world
+        .resource::<Assets<StandardMaterial>>()
+        .get(material)
+        .unwrap()
+        .base_color_texture;
+    assert_eq!(shown.as_ref(), Some(source));
+}
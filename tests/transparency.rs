@@ -0,0 +1,153 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext, EguiInput};
+use bevy_spatial_egui::{transparency::SpatialEguiTransparentWindow, SpatialEguiWindowConfig};
+use common::{spawn_pointer, spawn_window, take_events, test_app};
+
+fn hidden_children(app: &App, window: Entity) -> usize {
+    app.world()
+        .get::<Children>(window)
+        .into_iter()
+        .flatten()
+        .filter(|child| app.world().get::<Visibility>(**child) == Some(&Visibility::Hidden))
+        .count()
+}
+
+#[test]
+fn transparent_windows_only_show_egui() {
+    let mut app = test_app();
+    let window = app
+        .world_mut()
+        .spawn((
+            SpatialEguiWindowConfig::default(),
+            SpatialEguiTransparentWindow::default(),
+            EguiContext::default(),
+            EguiInput::default(),
+        ))
+        .id();
+    app.update();
+    app.update();
+
+    let handle = app.world().get::<Handle<StandardMaterial>>(window).unwrap();
+    let material = app
+        .world()
+        .resource::<Assets<StandardMaterial>>()
+        .get(handle)
+        .unwrap();
+    assert_eq!(material.alpha_mode, AlphaMode::Premultiplied);
+    assert_eq!(hidden_children(&app, window), 1);
+
+    app.world_mut()
+        .entity_mut(window)
+        .remove::<SpatialEguiTransparentWindow>();
+    app.update();
+    assert_eq!(hidden_children(&app, window), 0);
+}
+
+/// Draws a 100x100 point area in the top left corner of every window, the rest stays empty
+fn draw_hud(mut contexts: Query<&mut EguiContext>) {
+    for mut ctx in &mut contexts {
+        let raw_input = egui::RawInput {
+            screen_rect: Some(egui::Rect::from_min_size(
+                egui::Pos2::ZERO,
+                egui::vec2(512.0, 512.0),
+            )),
+            ..default()
+        };
+        let _ = ctx.get_mut().run(raw_input, |ctx| {
+            egui::Area::new(egui::Id::new("hud"))
+                .fixed_pos(egui::Pos2::ZERO)
+                .show(ctx, |ui| {
+                    ui.allocate_space(egui::vec2(100.0, 100.0));
+                });
+        });
+    }
+}
+
+#[test]
+fn input_goes_through_the_empty_parts_of_transparent_windows() {
+    let mut app = test_app();
+    app.add_systems(Update, draw_hud);
+    let window = spawn_window(&mut app);
+    app.world_mut()
+        .entity_mut(window)
+        .insert(SpatialEguiTransparentWindow::default());
+    // new areas only become visible after a few passes
+    for _ in 0..3 {
+        app.update();
+    }
+
+    // egui (384, 384), where nothing is drawn
+    let pointer = spawn_pointer(&mut app, window, Vec3::new(-0.25, -0.25, -1.0));
+    take_events(&mut app, window);
+    app.update();
+    assert_eq!(
+        take_events(&mut app, window),
+        vec![egui::Event::PointerGone]
+    );
+
+    // egui (51.2, 51.2), on the area
+    app.world_mut()
+        .get_mut::<Transform>(pointer)
+        .unwrap()
+        .translation = Vec3::new(0.4, 0.4, -1.0);
+    app.update();
+    app.update();
+    let events = take_events(&mut app, window);
+    assert!(
+        events.iter().any(|event| matches!(
+            event,
+            egui::Event::PointerMoved(pos) if (*pos - egui::pos2(51.2, 51.2)).length() < 1e-3
+        )),
+        "{events:?}"
+    );
+}
+
+/// Fills every window with a transparent central panel holding a button
+fn draw_central_panel(mut contexts: Query<&mut EguiContext>) {
+    for mut ctx in &mut contexts {
+        let raw_input = egui::RawInput {
+            screen_rect: Some(egui::Rect::from_min_size(
+                egui::Pos2::ZERO,
+                egui::vec2(512.0, 512.0),
+            )),
+            ..default()
+        };
+        let _ = ctx.get_mut().run(raw_input, |ctx| {
+            egui::CentralPanel::default()
+                .frame(egui::Frame::none())
+                .show(ctx, |ui| {
+                    ui.centered_and_justified(|ui| {
+                        let _ = ui.button("click");
+                    });
+                });
+        });
+    }
+}
+
+#[test]
+fn central_panels_of_transparent_windows_take_input() {
+    let mut app = test_app();
+    app.add_systems(Update, draw_central_panel);
+    let window = spawn_window(&mut app);
+    app.world_mut()
+        .entity_mut(window)
+        .insert(SpatialEguiTransparentWindow::default());
+    for _ in 0..3 {
+        app.update();
+    }
+
+    // egui (384, 384), on the button filling the panel
+    spawn_pointer(&mut app, window, Vec3::new(-0.25, -0.25, -1.0));
+    take_events(&mut app, window);
+    app.update();
+    let events = take_events(&mut app, window);
+    assert!(
+        events.iter().any(|event| matches!(
+            event,
+            egui::Event::PointerMoved(pos) if (*pos - egui::pos2(384.0, 384.0)).length() < 1e-3
+        )),
+        "{events:?}"
+    );
+}